@@ -0,0 +1,533 @@
+//! Simplified accessibility computations: roles, names, focus order and the accessibility tree.
+//!
+//! Roles and names are approximated from the markup alone, following the HTML-AAM and
+//! accname specifications for the common cases. No styles are applied.
+
+use ego_tree::NodeRef;
+
+use crate::element_ref::is_hidden;
+use crate::{ElementRef, Html, Node};
+
+/// A node of the simplified accessibility tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AxNode<'a> {
+    /// The role, e.g. `link` or `navigation`.
+    pub role: &'a str,
+
+    /// The accessible name.
+    pub name: Option<String>,
+
+    /// The element, or `None` for the document node.
+    pub element: Option<ElementRef<'a>>,
+
+    /// The child nodes.
+    pub children: Vec<AxNode<'a>>,
+}
+
+impl Html {
+    /// Builds a simplified accessibility tree of the document.
+    ///
+    /// The root node has the `document` role and is named by the `<title>`. Elements without a
+    /// role, such as plain `<div>` wrappers, or with the `presentation` or `none` role are
+    /// pruned and their children are attached to their closest remaining ancestor. Elements
+    /// which are `hidden`, `aria-hidden="true"` or never rendered are removed with their
+    /// descendants.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <nav><div><a href="/">Home</a></div><a href="/about">About</a></nav>
+    /// "#);
+    /// let tree = document.accessibility_tree();
+    ///
+    /// let nav = &tree.children[0];
+    /// assert_eq!(nav.role, "navigation");
+    /// assert_eq!(nav.children[1].name.as_deref(), Some("About"));
+    /// ```
+    pub fn accessibility_tree(&self) -> AxNode<'_> {
+        let mut children = Vec::new();
+        ax_children(self.tree.root(), &mut children);
+
+        let title = self
+            .tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|element| element.value().name() == "title")
+            .map(|title| normalize_whitespace(&title.text().collect::<String>()))
+            .filter(|title| !title.is_empty());
+
+        AxNode {
+            role: "document",
+            name: title,
+            element: None,
+            children,
+        }
+    }
+
+    /// Returns the focusable elements in sequential focus navigation order.
+    ///
+    /// Elements with a positive `tabindex` come first in ascending order, followed by the
+    /// elements with a `tabindex` of zero or focusable by default, in document order. Elements
+    /// with a negative `tabindex` are excluded.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <a href="/">Home</a><input id="q" tabindex="1"><button tabindex="-1">X</button>
+    /// "#);
+    /// let order: Vec<_> = document.tab_order().iter().map(|e| e.value().name()).collect();
+    ///
+    /// assert_eq!(order, ["input", "a"]);
+    /// ```
+    pub fn tab_order(&self) -> Vec<ElementRef<'_>> {
+        let mut focusable: Vec<_> = self
+            .tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|element| element.is_focusable())
+            .filter_map(|element| {
+                let tabindex = element.tabindex().unwrap_or(0);
+                (tabindex >= 0).then_some((tabindex, element))
+            })
+            .collect();
+
+        // A stable sort keeps document order among equal tab indices.
+        focusable.sort_by_key(|&(tabindex, _)| if tabindex > 0 { tabindex } else { i32::MAX });
+
+        focusable.into_iter().map(|(_, element)| element).collect()
+    }
+}
+
+fn ax_children<'a>(node: NodeRef<'a, Node>, out: &mut Vec<AxNode<'a>>) {
+    for child in node.children().filter_map(ElementRef::wrap) {
+        if is_excluded(child) {
+            continue;
+        }
+
+        match child.computed_role() {
+            Some(role) if role != "presentation" && role != "none" => {
+                let mut children = Vec::new();
+                ax_children(*child, &mut children);
+                out.push(AxNode {
+                    role,
+                    name: child.accessible_name(),
+                    element: Some(child),
+                    children,
+                });
+            }
+            _ => ax_children(*child, out),
+        }
+    }
+}
+
+fn is_excluded(element: ElementRef) -> bool {
+    matches!(
+        element.value().name(),
+        "head" | "script" | "style" | "template" | "noscript"
+    ) || element.attr("hidden").is_some()
+        || element
+            .attr("aria-hidden")
+            .is_some_and(|hidden| hidden.trim().eq_ignore_ascii_case("true"))
+}
+
+const HTML_WHITESPACE: &[char] = &['\t', '\n', '\x0C', '\r', ' '];
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl<'a> ElementRef<'a> {
+    /// Returns the ARIA role of this element.
+    ///
+    /// The first token of an explicit `role` attribute takes precedence over the implicit role
+    /// of the element. Returns `None` for elements without a role, such as `<div>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<a href="/">Home</a><div role="tab">1</div>"#);
+    /// let mut elements = fragment.root_element().child_elements();
+    ///
+    /// assert_eq!(elements.next().unwrap().computed_role(), Some("link"));
+    /// assert_eq!(elements.next().unwrap().computed_role(), Some("tab"));
+    /// ```
+    pub fn computed_role(&self) -> Option<&'a str> {
+        if let Some(role) = self.attr("role").and_then(|r| r.split_whitespace().next()) {
+            return Some(role);
+        }
+
+        let element = self.value();
+        let role = match element.name() {
+            "a" | "area" if element.attr("href").is_some() => "link",
+            "article" => "article",
+            "aside" => "complementary",
+            "blockquote" => "blockquote",
+            "button" => "button",
+            "datalist" => "listbox",
+            "dd" => "definition",
+            "details" | "fieldset" => "group",
+            "dialog" => "dialog",
+            "dt" => "term",
+            "figure" => "figure",
+            "footer" => "contentinfo",
+            "form" => "form",
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+            "header" => "banner",
+            "hr" => "separator",
+            "img" if element.attr("alt") == Some("") => "presentation",
+            "img" => "img",
+            "input" => match element.attr("type").map(str::to_ascii_lowercase).as_deref() {
+                Some("button" | "image" | "reset" | "submit") => "button",
+                Some("checkbox") => "checkbox",
+                Some("radio") => "radio",
+                Some("range") => "slider",
+                Some("number") => "spinbutton",
+                Some("search") => "searchbox",
+                None | Some("" | "email" | "tel" | "text" | "url") => "textbox",
+                _ => return None,
+            },
+            "li" => "listitem",
+            "main" => "main",
+            "menu" | "ol" | "ul" => "list",
+            "meter" => "meter",
+            "nav" => "navigation",
+            "option" => "option",
+            "output" => "status",
+            "p" => "paragraph",
+            "progress" => "progressbar",
+            "section" => "region",
+            "select" if element.attr("multiple").is_some() => "listbox",
+            "select" => "combobox",
+            "table" => "table",
+            "tbody" | "tfoot" | "thead" => "rowgroup",
+            "td" => "cell",
+            "textarea" => "textbox",
+            "th" => "columnheader",
+            "tr" => "row",
+            _ => return None,
+        };
+        Some(role)
+    }
+
+    /// Returns the accessible name of this element.
+    ///
+    /// In order of precedence, the name is taken from `aria-labelledby`, `aria-label`, the
+    /// native labelling mechanism (such as `alt`, `<label>` or `<caption>`), the text content
+    /// for roles named from content (such as links and buttons) and finally `title`.
+    /// Whitespace is normalized and empty names are `None`.
+    pub fn accessible_name(&self) -> Option<String> {
+        let non_empty = |name: String| Some(name).filter(|name| !name.is_empty());
+        let text = |element: ElementRef| normalize_whitespace(&element.text().collect::<String>());
+
+        if let Some(ids) = self.attr("aria-labelledby") {
+            let root = self.ancestors().last().unwrap_or(**self);
+            let labels: Vec<_> = ids
+                .split_whitespace()
+                .filter_map(|id| {
+                    root.descendants()
+                        .filter_map(ElementRef::wrap)
+                        .find(|e| e.value().id() == Some(id))
+                })
+                .map(text)
+                .collect();
+            if let Some(name) = non_empty(labels.join(" ")) {
+                return Some(name);
+            }
+        }
+
+        if let Some(name) = self.attr("aria-label").map(normalize_whitespace) {
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+
+        let element = self.value();
+        let input_type = element.attr("type").map(str::to_ascii_lowercase);
+        let native = match element.name() {
+            "img" | "area" => element.attr("alt").map(normalize_whitespace),
+            "input" if input_type.as_deref() == Some("image") => {
+                element.attr("alt").map(normalize_whitespace)
+            }
+            "input" if matches!(input_type.as_deref(), Some("button" | "submit" | "reset")) => {
+                element.attr("value").map(normalize_whitespace)
+            }
+            "input" | "select" | "textarea" => self.label_for().map(text),
+            "fieldset" => self.child_with_name("legend").map(text),
+            "figure" => self.child_with_name("figcaption").map(text),
+            "table" => self.child_with_name("caption").map(text),
+            _ => None,
+        };
+        if let Some(name) = native.and_then(non_empty) {
+            return Some(name);
+        }
+
+        let named_from_content = matches!(
+            self.computed_role(),
+            Some(
+                "button"
+                    | "cell"
+                    | "checkbox"
+                    | "columnheader"
+                    | "heading"
+                    | "link"
+                    | "menuitem"
+                    | "option"
+                    | "radio"
+                    | "row"
+                    | "switch"
+                    | "tab"
+                    | "tooltip"
+            )
+        );
+        if named_from_content {
+            if let Some(name) = non_empty(text(*self)) {
+                return Some(name);
+            }
+        }
+
+        self.attr("title")
+            .map(normalize_whitespace)
+            .and_then(non_empty)
+    }
+
+    /// Returns the value of the `tabindex` attribute, if it is a valid integer.
+    pub fn tabindex(&self) -> Option<i32> {
+        let value = self.attr("tabindex")?.trim_start_matches(HTML_WHITESPACE);
+        let end = value
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && (c == '-' || c == '+'))))
+            .map_or(value.len(), |(i, _)| i);
+        value[..end].parse().ok()
+    }
+
+    /// Returns true if this element can be focused.
+    ///
+    /// Links, form controls and other interactive elements are focusable by default, as are
+    /// elements with a `tabindex`. Disabled form controls and elements which are `hidden` or
+    /// `inert`, or have such an ancestor, are not.
+    pub fn is_focusable(&self) -> bool {
+        let element = self.value();
+        let disabled = matches!(
+            element.name(),
+            "button" | "input" | "select" | "textarea" | "fieldset" | "optgroup" | "option"
+        ) && element.attr("disabled").is_some();
+        let hidden = std::iter::once(*self)
+            .chain(self.ancestors().filter_map(ElementRef::wrap))
+            .any(|element| element.attr("hidden").is_some() || element.attr("inert").is_some());
+        if disabled || hidden {
+            return false;
+        }
+
+        if self.tabindex().is_some() {
+            return true;
+        }
+
+        match element.name() {
+            "a" | "area" => element.attr("href").is_some(),
+            "input" => !element
+                .attr("type")
+                .is_some_and(|t| t.eq_ignore_ascii_case("hidden")),
+            "button" | "select" | "textarea" | "iframe" => true,
+            "audio" | "video" => element.attr("controls").is_some(),
+            "summary" => self
+                .parent()
+                .and_then(ElementRef::wrap)
+                .is_some_and(|details| {
+                    details.value().name() == "details"
+                        && details
+                            .child_elements()
+                            .find(|child| child.value().name() == "summary")
+                            == Some(*self)
+                }),
+            _ => element
+                .attr("contenteditable")
+                .is_some_and(|editable| !editable.eq_ignore_ascii_case("false")),
+        }
+    }
+
+    /// Returns the text of this element which is neither hidden nor excluded from the
+    /// accessibility tree, with whitespace collapsed.
+    ///
+    /// Text is skipped inside `aria-hidden="true"` and `hidden` elements, elements hidden by an
+    /// inline `display: none` or `visibility: hidden` style, and elements which are never
+    /// rendered, such as `<script>`. The text is empty if this element or an ancestor is hidden.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<button><span aria-hidden="true">★</span> Save</button>"#);
+    /// let button = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(button.visible_accessible_text(), "Save");
+    /// ```
+    pub fn visible_accessible_text(&self) -> String {
+        fn collect<'a>(node: NodeRef<'a, Node>, out: &mut Vec<&'a str>) {
+            for child in node.children() {
+                match child.value() {
+                    Node::Text(text) => out.push(text),
+                    Node::Element(_) => {
+                        let element = ElementRef::wrap(child).unwrap();
+                        if !is_excluded(element) && !is_hidden(element) {
+                            collect(child, out);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let hidden = std::iter::once(*self)
+            .chain(self.ancestors().filter_map(ElementRef::wrap))
+            .any(|element| is_excluded(element) || is_hidden(element));
+        if hidden {
+            return String::new();
+        }
+        let mut text = Vec::new();
+        collect(**self, &mut text);
+        normalize_whitespace(&text.concat())
+    }
+
+    fn child_with_name(&self, name: &str) -> Option<ElementRef<'a>> {
+        self.child_elements()
+            .find(|child| child.value().name() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AxNode;
+    use crate::{Html, Selector};
+
+    fn outline<'a>(node: &AxNode<'a>) -> Vec<(&'a str, Option<String>, usize)> {
+        let mut nodes = vec![(node.role, node.name.clone(), node.children.len())];
+        for child in &node.children {
+            nodes.extend(outline(child));
+        }
+        nodes
+    }
+
+    #[test]
+    fn visible_accessible_text_skips_hidden() {
+        let html = Html::parse_document(
+            r#"<main id="main">
+                <h1>Title<span aria-hidden="true"> ¶</span></h1>
+                <p>Shown <span style="display: none">css-hidden</span>text.</p>
+                <p hidden>Hidden</p>
+                <script>var x;</script>
+                <div aria-hidden="TRUE"><p id="inner">Decorative</p></div>
+                <p aria-hidden="false">Still shown</p>
+            </main>"#,
+        );
+        let text = |id: &str| {
+            html.select(&Selector::parse(&format!("#{}", id)).unwrap())
+                .next()
+                .unwrap()
+                .visible_accessible_text()
+        };
+
+        assert_eq!(text("main"), "Title Shown text. Still shown");
+        assert_eq!(text("inner"), "");
+    }
+
+    #[test]
+    fn nav_with_links() {
+        let html = Html::parse_document(
+            r#"<title>Site</title>
+            <nav aria-label="Main">
+                <ul role="presentation">
+                    <li role="none"><a href="/">Home</a></li>
+                    <li role="none"><div><a href="/docs" aria-label="Documentation">Docs</a></div></li>
+                    <li role="none" aria-hidden="true"><a href="/secret">Secret</a></li>
+                </ul>
+            </nav>
+            <script>ignored()</script>"#,
+        );
+        let tree = html.accessibility_tree();
+        assert_eq!(
+            outline(&tree),
+            [
+                ("document", Some("Site".to_owned()), 1),
+                ("navigation", Some("Main".to_owned()), 2),
+                ("link", Some("Home".to_owned()), 0),
+                ("link", Some("Documentation".to_owned()), 0),
+            ]
+        );
+        assert_eq!(
+            tree.children[0].children[0].element.unwrap().attr("href"),
+            Some("/")
+        );
+    }
+
+    #[test]
+    fn tab_order_puts_positive_tabindex_first() {
+        let html = Html::parse_document(
+            r#"<a id="a" href="/">a</a>
+            <div id="b" tabindex="0">b</div>
+            <button id="c" tabindex="2">c</button>
+            <input id="d" tabindex=" 1px">
+            <input id="e" type="hidden">
+            <button id="f" disabled>f</button>
+            <span id="g" tabindex="-1">g</span>
+            <div hidden><a id="h" href="/">h</a></div>
+            <details><summary id="i">i</summary><summary id="j">j</summary></details>
+            <a id="k">k</a>
+            <div id="l" contenteditable>l</div>
+            <textarea id="m" tabindex="1"></textarea>"#,
+        );
+        let ids: Vec<_> = html
+            .tab_order()
+            .iter()
+            .map(|e| e.value().id().unwrap())
+            .collect();
+        assert_eq!(ids, ["d", "m", "c", "a", "b", "i", "l"]);
+
+        let g = html.select(&Selector::parse("#g").unwrap()).next().unwrap();
+        assert!(g.is_focusable());
+        assert_eq!(g.tabindex(), Some(-1));
+    }
+
+    #[test]
+    fn roles_and_names() {
+        let fragment = Html::parse_fragment(
+            r#"<span id="l1">First</span><span id="l2">name</span>
+            <input id="a" aria-labelledby="l1 l2">
+            <label for="b">Email</label><input id="b" type="email">
+            <input id="c" type="submit" value="Go">
+            <img id="d" src="x.png" alt="A cat">
+            <img id="e" src="y.png" alt="">
+            <button id="f" title="Close"></button>
+            <h2 id="g">  Section
+                title </h2>
+            <div id="h">no role</div>
+            <input id="i" type="hidden">
+            <input id="j" type="SUBMIT" value="Send">
+            <input id="k" type="Image" alt="Search">"#,
+        );
+        let roles_and_names: Vec<_> = fragment
+            .select(&Selector::parse("[id]:not(span)").unwrap())
+            .map(|e| (e.computed_role(), e.accessible_name()))
+            .collect();
+        assert_eq!(
+            roles_and_names,
+            [
+                (Some("textbox"), Some("First name".to_owned())),
+                (Some("textbox"), Some("Email".to_owned())),
+                (Some("button"), Some("Go".to_owned())),
+                (Some("img"), Some("A cat".to_owned())),
+                (Some("presentation"), None),
+                (Some("button"), Some("Close".to_owned())),
+                (Some("heading"), Some("Section title".to_owned())),
+                (None, None),
+                (None, None),
+                (Some("button"), Some("Send".to_owned())),
+                (Some("button"), Some("Search".to_owned())),
+            ]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use html5ever::Namespace;
 use selectors::{
-    attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint},
+    attr::{
+        AttrSelectorOperation, AttrSelectorOperator, CaseSensitivity, NamespaceConstraint,
+        SELECTOR_WHITESPACE,
+    },
     matching, Element, OpaqueElement,
 };
 
@@ -80,6 +83,26 @@ impl<'a> Element for ElementRef<'a> {
         local_name: &CssLocalName,
         operation: &AttrSelectorOperation<&CssString>,
     ) -> bool {
+        // Prefix, suffix and substring selectors with an empty value represent nothing, as do
+        // `~=` selectors whose value is empty or contains whitespace.
+        if let AttrSelectorOperation::WithValue {
+            operator, value, ..
+        } = *operation
+        {
+            let never_matches = match operator {
+                AttrSelectorOperator::Prefix
+                | AttrSelectorOperator::Suffix
+                | AttrSelectorOperator::Substring => value.0.is_empty(),
+                AttrSelectorOperator::Includes => {
+                    value.0.is_empty() || value.0.contains(SELECTOR_WHITESPACE)
+                }
+                AttrSelectorOperator::Equal | AttrSelectorOperator::DashMatch => false,
+            };
+            if never_matches {
+                return false;
+            }
+        }
+
         self.value().attrs.iter().any(|(key, value)| {
             !matches!(*ns, NamespaceConstraint::Specific(url) if *url != key.ns)
                 && local_name.0 == key.local
@@ -122,6 +145,7 @@ impl<'a> Element for ElementRef<'a> {
         self.value().has_class(&name.0, case_sensitivity)
     }
 
+    // Whitespace-only text counts as content, while comments and processing instructions do not.
     fn is_empty(&self) -> bool {
         !self
             .children()
@@ -130,7 +154,7 @@ impl<'a> Element for ElementRef<'a> {
 
     fn is_root(&self) -> bool {
         self.parent()
-            .map_or(false, |parent| parent.value().is_document())
+            .is_some_and(|parent| parent.value().is_document())
     }
 
     fn apply_selector_flags(&self, _flags: matching::ElementSelectorFlags) {}
@@ -179,6 +203,128 @@ mod tests {
         assert!(!element.is_link());
     }
 
+    #[test]
+    fn test_attr_operators() {
+        let html = r#"
+            <p id="1" data-x="foo-bar baz"></p>
+            <p id="2" data-x="FOO"></p>
+            <p id="3" data-x=""></p>
+            <p id="4" data-x="foobar"></p>
+            <p id="5"></p>
+        "#;
+        let fragment = Html::parse_fragment(html);
+        let ids = |selector: &str| {
+            fragment
+                .select(&Selector::parse(selector).unwrap())
+                .map(|element| element.value().id().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("[data-x]"), ["1", "2", "3", "4"]);
+        assert_eq!(ids("[DATA-X=FOO]"), ["2"]);
+        assert_eq!(ids(r#"[data-x=""]"#), ["3"]);
+        assert_eq!(ids("[data-x=foo]"), Vec::<&str>::new());
+        assert_eq!(ids("[data-x=foo i]"), ["2"]);
+
+        assert_eq!(ids("[data-x^=foo]"), ["1", "4"]);
+        assert_eq!(ids("[data-x^=foo i]"), ["1", "2", "4"]);
+        assert_eq!(ids(r#"[data-x^=""]"#), Vec::<&str>::new());
+
+        assert_eq!(ids("[data-x$=bar]"), ["4"]);
+        assert_eq!(ids("[data-x$=AZ i]"), ["1"]);
+        assert_eq!(ids(r#"[data-x$=""]"#), Vec::<&str>::new());
+
+        assert_eq!(ids("[data-x*=o-b]"), ["1"]);
+        assert_eq!(ids("[data-x*=OO i]"), ["1", "2", "4"]);
+        assert_eq!(ids(r#"[data-x*=""]"#), Vec::<&str>::new());
+
+        assert_eq!(ids("[data-x~=baz]"), ["1"]);
+        assert_eq!(ids("[data-x~=foo]"), Vec::<&str>::new());
+        assert_eq!(ids("[data-x~=foo-bar]"), ["1"]);
+        assert_eq!(ids("[data-x~=foo i]"), ["2"]);
+        assert_eq!(ids(r#"[data-x~=""]"#), Vec::<&str>::new());
+        assert_eq!(ids(r#"[data-x~="foo-bar baz"]"#), Vec::<&str>::new());
+
+        assert_eq!(ids("[data-x|=foo]"), ["1"]);
+        assert_eq!(ids("[data-x|=foo i]"), ["1", "2"]);
+        assert_eq!(ids("[data-x|=foobar]"), ["4"]);
+        assert_eq!(ids(r#"[data-x|=""]"#), ["3"]);
+    }
+
+    #[test]
+    fn test_nth_last_pseudo_classes() {
+        let html = r#"
+            <div>
+                text<p id="1"></p><!-- c --><span id="2"></span>
+                <p id="3"></p> more text <span id="4"></span><p id="5"></p>
+            </div>
+        "#;
+        let fragment = Html::parse_fragment(html);
+        let ids = |selector: &str| {
+            fragment
+                .select(&Selector::parse(selector).unwrap())
+                .map(|element| element.value().id().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("div > :nth-last-child(2)"), ["4"]);
+        assert_eq!(ids("div > :nth-last-child(odd)"), ["1", "3", "5"]);
+        assert_eq!(ids("div > :nth-last-child(-n + 2)"), ["4", "5"]);
+        assert_eq!(ids("p:nth-last-child(1)"), ["5"]);
+        assert_eq!(ids("span:nth-last-child(1)"), Vec::<&str>::new());
+
+        assert_eq!(ids("p:nth-last-of-type(2)"), ["3"]);
+        assert_eq!(ids("span:nth-last-of-type(1)"), ["4"]);
+        assert_eq!(ids("span:nth-last-of-type(2)"), ["2"]);
+        assert_eq!(ids("p:nth-last-of-type(n + 2)"), ["1", "3"]);
+        assert_eq!(ids("div > :last-of-type"), ["4", "5"]);
+    }
+
+    #[test]
+    fn test_empty_pseudo_class() {
+        let html = r#"
+            <p id="1"></p>
+            <p id="2"> </p>
+            <p id="3"><!--x--></p>
+            <p id="4"><!--x--> <!--y--></p>
+            <p id="5"><br></p>
+            <p id="6">text</p>
+        "#;
+        let fragment = Html::parse_fragment(html);
+        let ids: Vec<_> = fragment
+            .select(&Selector::parse("p:empty").unwrap())
+            .map(|element| element.value().id().unwrap())
+            .collect();
+        assert_eq!(ids, ["1", "3"]);
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let html = r#"
+            <a id="1" href="/">html</a>
+            <svg><a id="2" xlink:href="/">svg</a></svg>
+            <math><mi id="3">x</mi></math>
+        "#;
+        let fragment = Html::parse_fragment(html);
+        let ids = |selector: &str| {
+            fragment
+                .select(&Selector::parse(selector).unwrap())
+                .filter_map(|element| element.value().id())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("a"), ["1", "2"]);
+        assert_eq!(ids("*|a"), ["1", "2"]);
+        assert_eq!(ids("|a"), Vec::<&str>::new());
+        assert_eq!(ids("html|a"), ["1"]);
+        assert_eq!(ids("svg|a"), ["2"]);
+        assert_eq!(ids("svg|*"), ["2"]);
+        assert_eq!(ids("math|mi, mathml|mi"), ["3"]);
+        assert_eq!(ids("[xlink|href]"), ["2"]);
+        assert_eq!(ids("[|href]"), ["1"]);
+        assert!(Selector::parse("foo|a").is_err());
+    }
+
     #[test]
     fn test_has_class() {
         let html = "<p class='my_class'>hey there</p>";
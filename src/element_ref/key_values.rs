@@ -0,0 +1,108 @@
+use super::ElementRef;
+
+impl<'a> ElementRef<'a> {
+    /// Extracts key-value pairs from common layouts within this element, in document order.
+    ///
+    /// The supported layouts are:
+    ///
+    /// 1. `<dt>` keys followed by `<dd>` values, with each `<dd>` paired with its `<dt>`,
+    /// 2. table rows with exactly two `<th>` or `<td>` cells,
+    /// 3. `<li>` items starting with a `<strong>` or `<b>` label followed by the value.
+    ///
+    /// Whitespace is collapsed and a colon between the key and the value is removed. Pairs with
+    /// an empty key are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<dl><dt>Weight:</dt><dd>2 kg</dd></dl>");
+    /// let dl = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(dl.key_values(), [("Weight".to_owned(), "2 kg".to_owned())]);
+    /// ```
+    pub fn key_values(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for element in self.descendent_elements() {
+            let pair = match element.value().name() {
+                "dd" => element
+                    .prev_siblings()
+                    .filter_map(ElementRef::wrap)
+                    .find(|sibling| sibling.value().name() == "dt")
+                    .map(|dt| (text_of(dt), text_of(element))),
+                "tr" => {
+                    let mut cells = element
+                        .child_elements()
+                        .filter(|cell| matches!(cell.value().name(), "th" | "td"));
+                    match (cells.next(), cells.next(), cells.next()) {
+                        (Some(key), Some(value), None) => Some((text_of(key), text_of(value))),
+                        _ => None,
+                    }
+                }
+                "li" => labeled_item(element),
+                _ => None,
+            };
+            if let Some((key, value)) = pair {
+                let key = key.trim_end_matches(':').trim_end();
+                if !key.is_empty() {
+                    pairs.push((key.to_owned(), value));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+fn labeled_item(li: ElementRef) -> Option<(String, String)> {
+    let mut children = li
+        .children()
+        .filter(|child| !matches!(child.value().as_text(), Some(text) if text.trim().is_empty()));
+    let label = children
+        .next()
+        .and_then(ElementRef::wrap)
+        .filter(|label| matches!(label.value().name(), "strong" | "b"))?;
+
+    let value: String = children
+        .flat_map(|child| child.descendants())
+        .filter_map(|node| node.value().as_text())
+        .map(|text| &**text)
+        .collect();
+    Some((
+        text_of(label),
+        normalize(value.trim_start().trim_start_matches(':')),
+    ))
+}
+
+fn text_of(element: ElementRef) -> String {
+    normalize(&element.text().collect::<String>())
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn key_values_from_each_layout() {
+        let expected = [
+            ("Color".to_owned(), "Deep red".to_owned()),
+            ("Size".to_owned(), "L".to_owned()),
+        ];
+        for html in [
+            "<dl><dt>Color</dt><dd>Deep\n red</dd> <dt>Size:</dt><dd>L</dd></dl>",
+            "<table><tr><th>Color</th><td>Deep <i>red</i></td></tr>
+             <tr><th>Size</th><td>L</td></tr>
+             <tr><td colspan=3>Ignored</td></tr>
+             <tr><td>a</td><td>b</td><td>c</td></tr></table>",
+            "<ul><li> <strong>Color:</strong> Deep <em>red</em></li>
+             <li><b>Size</b>: L</li><li>Unlabeled</li><li>x <b>late</b></li></ul>",
+        ] {
+            let fragment = Html::parse_fragment(html);
+            let container = fragment.root_element().child_elements().next().unwrap();
+            assert_eq!(container.key_values(), expected, "{}", html);
+        }
+    }
+}
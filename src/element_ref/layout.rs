@@ -0,0 +1,234 @@
+use ego_tree::iter::Edge;
+
+use super::ElementRef;
+use crate::node::Element;
+use crate::stylesheet::Declarations;
+use crate::Node;
+
+/// Cheap signals about how an element is likely to be rendered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutHints {
+    /// The element or an ancestor is hidden by the `hidden` attribute, `aria-hidden="true"` or
+    /// an inline `display: none` or `visibility: hidden` style.
+    pub hidden: bool,
+
+    /// The element is or is inside a `<nav>`, `<footer>` or `<aside>`, or an element with the
+    /// equivalent role.
+    pub boilerplate: bool,
+
+    /// The share of the element's non-whitespace text which is inside links, from 0 to 1.
+    pub link_density: f32,
+}
+
+impl<'a> ElementRef<'a> {
+    /// Computes layout hints for ranking the content of this element.
+    ///
+    /// Only inline styles are considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"<p>Read <a href="/">more</a></p>"#);
+    /// let p = fragment.select(&Selector::parse("p").unwrap()).next().unwrap();
+    ///
+    /// let hints = p.layout_hints();
+    /// assert!(!hints.hidden);
+    /// assert_eq!(hints.link_density, 0.5);
+    /// ```
+    pub fn layout_hints(&self) -> LayoutHints {
+        let mut hidden = false;
+        let mut boilerplate = false;
+        for element in std::iter::once(*self).chain(self.ancestors().filter_map(ElementRef::wrap)) {
+            hidden = hidden || is_hidden(element);
+            boilerplate = boilerplate
+                || matches!(element.value().name(), "nav" | "footer" | "aside")
+                || element.attr("role").is_some_and(|role| {
+                    role.split_whitespace()
+                        .any(|r| matches!(r, "navigation" | "contentinfo" | "complementary"))
+                });
+        }
+
+        let mut text = 0;
+        let mut link_text = 0;
+        for node in self.descendants() {
+            if let Node::Text(t) = node.value() {
+                let length = t.chars().filter(|c| !c.is_whitespace()).count();
+                text += length;
+                let in_link = node
+                    .ancestors()
+                    .take_while(|ancestor| ancestor.id() != self.id())
+                    .chain(std::iter::once(**self))
+                    .any(|ancestor| {
+                        ancestor
+                            .value()
+                            .as_element()
+                            .is_some_and(|e| e.name() == "a")
+                    });
+                if in_link {
+                    link_text += length;
+                }
+            }
+        }
+
+        LayoutHints {
+            hidden,
+            boilerplate,
+            link_density: if text == 0 {
+                0.0
+            } else {
+                link_text as f32 / text as f32
+            },
+        }
+    }
+
+    /// Returns the ratio of text bytes to markup bytes in this element's HTML.
+    ///
+    /// Markup is counted as it would be serialized: tags with their attributes, and comments.
+    /// Text is counted without escaping. Content tends to have a higher ratio than navigation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<p>Hello, world</p>");
+    /// let p = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(p.text_to_tag_ratio(), 12.0 / 7.0);
+    /// ```
+    pub fn text_to_tag_ratio(&self) -> f32 {
+        let mut text = 0;
+        let mut markup = 0;
+        for edge in self.traverse() {
+            match edge {
+                Edge::Open(node) => match node.value() {
+                    Node::Text(t) => text += t.len(),
+                    Node::Element(e) => markup += start_tag_len(e),
+                    Node::Comment(c) => markup += "<!---->".len() + c.len(),
+                    _ => {}
+                },
+                Edge::Close(node) => {
+                    if let Node::Element(e) = node.value() {
+                        if !is_void(e) {
+                            markup += "</>".len() + e.name().len();
+                        }
+                    }
+                }
+            }
+        }
+        text as f32 / markup as f32
+    }
+}
+
+fn start_tag_len(element: &Element) -> usize {
+    let attrs: usize = element
+        .attrs()
+        .map(|(name, value)| r#" ="""#.len() + name.len() + value.len())
+        .sum();
+    "<>".len() + element.name().len() + attrs
+}
+
+fn is_void(element: &Element) -> bool {
+    matches!(
+        element.name(),
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+pub(crate) fn is_hidden(element: ElementRef) -> bool {
+    is_display_hidden(element)
+        || element
+            .attr("aria-hidden")
+            .is_some_and(|hidden| hidden.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Returns true if the element is not rendered, ignoring `aria-hidden`, which only hides it
+/// from assistive technology.
+pub(crate) fn is_display_hidden(element: ElementRef) -> bool {
+    if element.attr("hidden").is_some() {
+        return true;
+    }
+
+    element.attr("style").is_some_and(|style| {
+        let style = Declarations::parse(style);
+        let is = |name, value: &str| {
+            style.get(name).is_some_and(|v| {
+                v.trim_end_matches("!important")
+                    .trim()
+                    .eq_ignore_ascii_case(value)
+            })
+        };
+        is("display", "none") || is("visibility", "hidden")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Html, Selector};
+
+    #[test]
+    fn text_to_tag_ratio_ranks_content_above_navigation() {
+        let html = Html::parse_fragment(
+            r#"<p id="content">A long paragraph of article text, with <em>some</em> emphasis.<br></p>
+            <nav id="nav"><ul><li><a href="/">Home</a></li><li><a href="/about">About</a></li></ul></nav>
+            <div id="markup"><!--x--><img src="a.png"></div>"#,
+        );
+        let ratio = |id: &str| {
+            html.select(&Selector::parse(&format!("#{}", id)).unwrap())
+                .next()
+                .unwrap()
+                .text_to_tag_ratio()
+        };
+
+        assert_eq!(ratio("content"), 53.0 / 33.0);
+        assert!(ratio("nav") < 0.2);
+        assert_eq!(ratio("markup"), 0.0);
+    }
+
+    #[test]
+    fn hidden_boilerplate_and_link_density() {
+        let html = Html::parse_document(
+            r#"<article><p id="content">Some long article text <a href="/">link</a></p></article>
+            <footer style="color: gray; DISPLAY: none !important">
+                <ul id="links"><li><a href="/a">About</a></li><li><a href="/b">Jobs</a></li></ul>
+            </footer>
+            <div role="navigation"><p id="empty" aria-hidden="true"></p></div>
+            <p id="invisible" style="visibility:hidden">x</p>"#,
+        );
+        let hints = |id: &str| {
+            html.select(&Selector::parse(&format!("#{}", id)).unwrap())
+                .next()
+                .unwrap()
+                .layout_hints()
+        };
+
+        let content = hints("content");
+        assert!(!content.hidden);
+        assert!(!content.boilerplate);
+        assert_eq!(content.link_density, 4.0 / 23.0);
+
+        let links = hints("links");
+        assert!(links.hidden);
+        assert!(links.boilerplate);
+        assert_eq!(links.link_density, 1.0);
+
+        let empty = hints("empty");
+        assert!(empty.hidden);
+        assert!(empty.boilerplate);
+        assert_eq!(empty.link_density, 0.0);
+
+        assert!(hints("invisible").hidden);
+    }
+}
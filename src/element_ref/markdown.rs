@@ -0,0 +1,278 @@
+use ego_tree::NodeRef;
+
+use super::ElementRef;
+use crate::Node;
+
+impl<'a> ElementRef<'a> {
+    /// Converts this element and its descendants to Markdown.
+    ///
+    /// Headings, paragraphs, links, images, emphasis, inline code, preformatted blocks,
+    /// blockquotes, ordered and unordered lists and horizontal rules are converted. Other
+    /// elements contribute their content, and block-level ones separate paragraphs. Whitespace
+    /// is collapsed outside of `<pre>`; text is not escaped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<div><h2>Install</h2><p>Run <code>make</code>.</p></div>"#);
+    /// let div = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(div.to_markdown(), "## Install\n\nRun `make`.");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = Markdown::new("\n\n");
+        markdown.node(**self);
+        markdown.finish()
+    }
+}
+
+struct Markdown {
+    out: String,
+    separator: &'static str,
+    pending_break: bool,
+}
+
+impl Markdown {
+    fn new(separator: &'static str) -> Self {
+        Markdown {
+            out: String::new(),
+            separator,
+            pending_break: false,
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out.trim().to_owned()
+    }
+
+    fn render(node: NodeRef<Node>, separator: &'static str) -> String {
+        let mut markdown = Markdown::new(separator);
+        markdown.children(node);
+        markdown.finish()
+    }
+
+    fn children(&mut self, node: NodeRef<Node>) {
+        for child in node.children() {
+            self.node(child);
+        }
+    }
+
+    fn node(&mut self, node: NodeRef<Node>) {
+        match node.value() {
+            Node::Text(text) => self.text(text),
+            Node::Element(_) => self.element(ElementRef::new(node)),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        for (i, word) in text.split(char::is_whitespace).enumerate() {
+            if i > 0
+                && !self.pending_break
+                && !self.out.is_empty()
+                && !self.out.ends_with(char::is_whitespace)
+            {
+                self.out.push(' ');
+            }
+            self.inline(word);
+        }
+    }
+
+    fn inline(&mut self, markdown: &str) {
+        if markdown.is_empty() {
+            return;
+        }
+        if self.pending_break {
+            self.start_block();
+        }
+        self.out.push_str(markdown);
+    }
+
+    fn start_block(&mut self) {
+        self.out.truncate(self.out.trim_end().len());
+        if !self.out.is_empty() {
+            self.out.push_str(self.separator);
+        }
+        self.pending_break = false;
+    }
+
+    fn block(&mut self, markdown: &str) {
+        if !markdown.is_empty() {
+            self.start_block();
+            self.out.push_str(markdown);
+        }
+        self.pending_break = true;
+    }
+
+    fn wrapped(&mut self, element: ElementRef, delimiter: &str) {
+        let content = Markdown::render(*element, self.separator);
+        if !content.is_empty() {
+            self.inline(&format!("{}{}{}", delimiter, content, delimiter));
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        match name {
+            "head" | "script" | "style" | "template" | "noscript" => {}
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = element.heading_level().unwrap_or(1);
+                let content = Markdown::render(*element, " ");
+                self.block(&format!("{} {}", "#".repeat(level.into()), content));
+            }
+            "a" => {
+                let content = Markdown::render(*element, " ");
+                match element.attr("href") {
+                    Some(href) => self.inline(&format!("[{}]({})", content, href)),
+                    None => self.inline(&content),
+                }
+            }
+            "img" => {
+                if let Some(src) = element.attr("src") {
+                    let alt = element.attr("alt").unwrap_or_default();
+                    self.inline(&format!("![{}]({})", alt, src));
+                }
+            }
+            "strong" | "b" => self.wrapped(element, "**"),
+            "em" | "i" => self.wrapped(element, "*"),
+            "code" => {
+                let code: String = element.text().collect();
+                let delimiter = if code.contains('`') { "``" } else { "`" };
+                let padding = if code.starts_with('`') || code.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+                self.inline(&format!("{0}{1}{2}{1}{0}", delimiter, padding, code));
+            }
+            "br" => self.inline("  \n"),
+            "hr" => self.block("---"),
+            "pre" => {
+                let code: String = element.text().collect();
+                let language = element
+                    .child_elements()
+                    .find(|child| child.value().name() == "code")
+                    .and_then(|code| {
+                        code.value()
+                            .classes()
+                            .find_map(|class| class.strip_prefix("language-"))
+                    })
+                    .unwrap_or_default();
+                let fence = if code.contains("```") { "~~~" } else { "```" };
+                self.block(&format!(
+                    "{0}{1}\n{2}\n{0}",
+                    fence,
+                    language,
+                    code.trim_end_matches('\n')
+                ));
+            }
+            "blockquote" => {
+                let content = Markdown::render(*element, "\n\n");
+                let quoted: Vec<_> = content
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_owned()
+                        } else {
+                            format!("> {}", line)
+                        }
+                    })
+                    .collect();
+                self.block(&quoted.join("\n"));
+            }
+            "ul" | "ol" | "menu" => {
+                let mut number: usize = element
+                    .attr("start")
+                    .and_then(|start| start.trim().parse().ok())
+                    .unwrap_or(1);
+                let mut items = Vec::new();
+                for item in element
+                    .child_elements()
+                    .filter(|child| child.value().name() == "li")
+                {
+                    let marker = if name == "ol" {
+                        number += 1;
+                        format!("{}. ", number - 1)
+                    } else {
+                        "- ".to_owned()
+                    };
+                    let content = Markdown::render(*item, "\n");
+                    let mut lines = content.lines();
+                    let mut rendered = format!("{}{}", marker, lines.next().unwrap_or_default());
+                    for line in lines {
+                        rendered.push('\n');
+                        if !line.is_empty() {
+                            rendered.push_str(&" ".repeat(marker.len()));
+                            rendered.push_str(line);
+                        }
+                    }
+                    items.push(rendered);
+                }
+                self.block(&items.join("\n"));
+            }
+            "address" | "article" | "aside" | "body" | "dd" | "details" | "dialog" | "div"
+            | "dl" | "dt" | "fieldset" | "figcaption" | "figure" | "footer" | "form" | "header"
+            | "html" | "li" | "main" | "nav" | "p" | "section" | "summary" | "table" | "tbody"
+            | "td" | "tfoot" | "th" | "thead" | "tr" => {
+                let content = Markdown::render(*element, self.separator);
+                self.block(&content);
+            }
+            _ => self.children(*element),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Html, Selector};
+
+    #[test]
+    fn markdown_fixture() {
+        let html = Html::parse_document(
+            r#"<article>
+                <h1>Scraper <em>guide</em></h1>
+                <p>Read the
+                    <a href="https://docs.rs/scraper">documentation</a> and <strong>star</strong> it.</p>
+                <ul>
+                    <li>Parse</li>
+                    <li>Select
+                        <ol start="3"><li>by tag</li><li>by <code>class</code></li></ol>
+                    </li>
+                </ul>
+                <blockquote><p>Quote</p><p>More</p></blockquote>
+                <pre><code class="language-rust">let x = 1;
+println!("{}", x);
+</code></pre>
+                <script>ignored()</script>
+                trailing text
+            </article>"#,
+        );
+        let article = html
+            .select(&Selector::parse("article").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(
+            article.to_markdown(),
+            r#"# Scraper *guide*
+
+Read the [documentation](https://docs.rs/scraper) and **star** it.
+
+- Parse
+- Select
+  3. by tag
+  4. by `class`
+
+> Quote
+>
+> More
+
+```rust
+let x = 1;
+println!("{}", x);
+```
+
+trailing text"#
+        );
+    }
+}
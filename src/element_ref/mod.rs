@@ -1,15 +1,26 @@
 //! Element references.
 
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+use std::io;
 use std::iter::FusedIterator;
 use std::ops::Deref;
 
 use ego_tree::iter::{Edge, Traverse};
 use ego_tree::NodeRef;
-use html5ever::serialize::{serialize, SerializeOpts, TraversalScope};
+use html5ever::serialize::{serialize, HtmlSerializer, SerializeOpts, TraversalScope};
+use html5ever::{LocalName, QualName};
+use once_cell::sync::Lazy;
+use selectors::attr::CaseSensitivity;
 
 use crate::node::Element;
 use crate::{Node, Selector};
 
+static TABLE: Lazy<Selector> = Lazy::new(|| Selector::parse("table").unwrap());
+static LIST: Lazy<Selector> = Lazy::new(|| Selector::parse("ul, ol").unwrap());
+static FORM: Lazy<Selector> = Lazy::new(|| Selector::parse("form").unwrap());
+
 /// Wrapper around a reference to an element node.
 ///
 /// This wrapper implements the `Element` trait from the `selectors` crate, which allows it to be
@@ -38,7 +49,29 @@ impl<'a> ElementRef<'a> {
         self.node.value().as_element().unwrap()
     }
 
+    /// Returns the interned local name of this element.
+    ///
+    /// Comparing interned names is cheaper than comparing strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// use html5ever::local_name;
+    ///
+    /// let fragment = Html::parse_fragment("<p>a</p>");
+    /// let p = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(*p.local_name(), local_name!("p"));
+    /// ```
+    pub fn local_name(&self) -> &'a LocalName {
+        &self.value().name.local
+    }
+
     /// Returns an iterator over descendent elements matching a selector.
+    ///
+    /// Dynamic pseudo-classes such as `:hover` never match; see
+    /// [`Selector::has_dynamic_pseudo`].
     pub fn select<'b>(&self, selector: &'b Selector) -> Select<'a, 'b> {
         let mut inner = self.traverse();
         inner.next(); // Skip Edge::Open(self).
@@ -50,6 +83,194 @@ impl<'a> ElementRef<'a> {
         }
     }
 
+    /// Returns the last descendent element matching a selector.
+    ///
+    /// Descendants are visited in reverse document order, stopping at the first match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment("<ul><li>1</li><li>2</li><li>3</li></ul>");
+    /// let ul = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let last = ul.select_last(&Selector::parse("li").unwrap()).unwrap();
+    /// assert_eq!(last.inner_html(), "3");
+    /// ```
+    pub fn select_last(&self, selector: &Selector) -> Option<ElementRef<'a>> {
+        let mut node = last_descendant(**self);
+        while node != **self {
+            if let Some(element) = ElementRef::wrap(node) {
+                if selector.matches_with_scope(&element, Some(*self)) {
+                    return Some(element);
+                }
+            }
+            node = match node.prev_sibling() {
+                Some(sibling) => last_descendant(sibling),
+                None => node.parent()?,
+            };
+        }
+        None
+    }
+
+    /// Returns an iterator over descendent elements matching a selector, each paired with the
+    /// text node immediately before it, if any.
+    ///
+    /// This captures loose labels such as `Price: <b>9.99</b>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment("<p>Price: <b>9.99</b></p>");
+    /// let b = Selector::parse("b").unwrap();
+    ///
+    /// let (value, label) = fragment.root_element().select_with_preceding_text(&b).next().unwrap();
+    /// assert_eq!((value.inner_html().as_str(), label), ("9.99", Some("Price: ")));
+    /// ```
+    pub fn select_with_preceding_text<'b>(
+        &self,
+        selector: &'b Selector,
+    ) -> impl Iterator<Item = (ElementRef<'a>, Option<&'a str>)> + 'b
+    where
+        'a: 'b,
+    {
+        self.select(selector).map(|element| {
+            let text = element
+                .prev_sibling()
+                .and_then(|sibling| sibling.value().as_text())
+                .map(|text| &**text);
+            (element, text)
+        })
+    }
+
+    /// Returns an iterator over the parent elements of descendent elements matching a selector.
+    ///
+    /// Each parent is yielded once, in the order of its first matching child.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"
+    ///     <div class="product"><span class="price">1</span><span class="price">2</span></div>
+    ///     <div class="product"><span class="price">3</span></div>
+    /// "#);
+    /// let price = Selector::parse(".price").unwrap();
+    ///
+    /// assert_eq!(fragment.root_element().select_parents(&price).count(), 2);
+    /// ```
+    pub fn select_parents<'b>(
+        &self,
+        selector: &'b Selector,
+    ) -> impl Iterator<Item = ElementRef<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        let mut seen = HashSet::new();
+        self.select(selector)
+            .filter_map(|element| element.parent().and_then(ElementRef::wrap))
+            .filter(move |parent| seen.insert(parent.id()))
+    }
+
+    /// Pairs the text of descendent elements matching `label_selector` with the text of those
+    /// matching `value_selector`, in document order.
+    ///
+    /// Text is trimmed. Unpaired labels or values are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"
+    ///     <div class="k">Weight</div><div class="v">2 kg</div>
+    ///     <div class="k">Color</div><div class="v">Red</div>
+    /// "#);
+    /// let label = Selector::parse(".k").unwrap();
+    /// let value = Selector::parse(".v").unwrap();
+    ///
+    /// let pairs = fragment.root_element().labeled_values(&label, &value);
+    /// assert_eq!(pairs[1], ("Color".to_owned(), "Red".to_owned()));
+    /// ```
+    pub fn labeled_values(
+        &self,
+        label_selector: &Selector,
+        value_selector: &Selector,
+    ) -> Vec<(String, String)> {
+        let text = |element: ElementRef| element.text().collect::<String>().trim().to_owned();
+        self.select(label_selector)
+            .map(text)
+            .zip(self.select(value_selector).map(text))
+            .collect()
+    }
+
+    /// Returns true if any descendent element matches the selector.
+    ///
+    /// Stops at the first match instead of traversing the whole subtree.
+    pub fn exists(&self, selector: &Selector) -> bool {
+        self.select(selector).next().is_some()
+    }
+
+    /// Returns the nearest inclusive ancestor matching the selector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"<div class="card"><p><b>x</b></p></div>"#);
+    /// let b = fragment.select(&Selector::parse("b").unwrap()).next().unwrap();
+    ///
+    /// let card = b.closest(&Selector::parse(".card").unwrap()).unwrap();
+    /// assert_eq!(card.value().name(), "div");
+    /// ```
+    pub fn closest(&self, selector: &Selector) -> Option<ElementRef<'a>> {
+        std::iter::once(**self)
+            .chain(self.ancestors())
+            .filter_map(ElementRef::wrap)
+            .find(|element| selector.matches(element))
+    }
+
+    /// Returns the value of an attribute on the nearest inclusive ancestor where it is not empty,
+    /// with that element.
+    ///
+    /// This resolves attributes inherited by convention, such as `lang` or `data-*` context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let document = Html::parse_document(r#"<html lang="fr"><body><p lang="">Bonjour</p></body></html>"#);
+    /// let p = document.select(&Selector::parse("p").unwrap()).next().unwrap();
+    ///
+    /// let (lang, html) = p.inherited_attr("lang").unwrap();
+    /// assert_eq!((lang, html.value().name()), ("fr", "html"));
+    /// ```
+    pub fn inherited_attr(&self, name: &str) -> Option<(&'a str, ElementRef<'a>)> {
+        std::iter::once(*self)
+            .chain(self.ancestors().filter_map(ElementRef::wrap))
+            .find_map(|element| {
+                element
+                    .attr(name)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (value, element))
+            })
+    }
+
+    /// Returns the nearest inclusive ancestor `<table>`.
+    pub fn closest_table(&self) -> Option<ElementRef<'a>> {
+        self.closest(&TABLE)
+    }
+
+    /// Returns the nearest inclusive ancestor `<ul>` or `<ol>`.
+    pub fn closest_list(&self) -> Option<ElementRef<'a>> {
+        self.closest(&LIST)
+    }
+
+    /// Returns the nearest inclusive ancestor `<form>`.
+    pub fn closest_form(&self) -> Option<ElementRef<'a>> {
+        self.closest(&FORM)
+    }
+
     fn serialize(&self, traversal_scope: TraversalScope) -> String {
         let opts = SerializeOpts {
             scripting_enabled: false, // It's not clear what this does.
@@ -66,9 +287,166 @@ impl<'a> ElementRef<'a> {
         self.serialize(TraversalScope::IncludeNode)
     }
 
+    /// Writes the HTML of this element to a writer.
+    pub fn write_html<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let opts = SerializeOpts {
+            scripting_enabled: false, // It's not clear what this does.
+            traversal_scope: TraversalScope::IncludeNode,
+            create_missing_parent: false,
+        };
+        serialize(writer, self, opts)
+    }
+
     /// Returns the inner HTML of this element.
     pub fn inner_html(&self) -> String {
-        self.serialize(TraversalScope::ChildrenOnly(None))
+        // Naming the parent lets the serializer keep the content of e.g. `<script>` raw.
+        self.serialize(TraversalScope::ChildrenOnly(Some(
+            self.value().name.clone(),
+        )))
+    }
+
+    /// Returns a minimal HTML document with this element as the content of its body.
+    ///
+    /// The document declares UTF-8 and has a `<title>` if one is given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<p>Hi</p>");
+    /// let p = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     p.to_standalone_document(Some("Preview")),
+    ///     "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Preview</title></head>\
+    ///      <body><p>Hi</p></body></html>"
+    /// );
+    /// ```
+    pub fn to_standalone_document(&self, title: Option<&str>) -> String {
+        let mut document = String::from("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+        if let Some(title) = title {
+            document.push_str("<title>");
+            for c in title.chars() {
+                match c {
+                    '&' => document.push_str("&amp;"),
+                    '<' => document.push_str("&lt;"),
+                    '>' => document.push_str("&gt;"),
+                    '\u{a0}' => document.push_str("&nbsp;"),
+                    c => document.push(c),
+                }
+            }
+            document.push_str("</title>");
+        }
+        document.push_str("</head><body>");
+        document.push_str(&self.html());
+        document.push_str("</body></html>");
+        document
+    }
+
+    /// Returns the HTML of this element with the content of `<script>` and `<style>` elements
+    /// wrapped in CDATA sections, as required when embedding it in XHTML.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<div><script>if (a < b) f();</script></div>");
+    /// let div = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(div.html(), "<div><script>if (a < b) f();</script></div>");
+    /// assert_eq!(
+    ///     div.html_with_cdata(),
+    ///     "<div><script><![CDATA[if (a < b) f();]]></script></div>"
+    /// );
+    /// ```
+    pub fn html_with_cdata(&self) -> String {
+        let opts = SerializeOpts {
+            scripting_enabled: false, // It's not clear what this does.
+            traversal_scope: TraversalScope::IncludeNode,
+            create_missing_parent: false,
+        };
+        let mut buf = Vec::new();
+        let mut serializer = serializable::CdataSerializer::new(&mut buf, opts);
+        crate::node::serializable::serialize(**self, &mut serializer, TraversalScope::IncludeNode)
+            .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Returns the HTML of this element, putting each attribute of a start tag on its own
+    /// indented line when the tag would be longer than `width` characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(
+    ///     r#"<div><a href="/some/rather/long/path">Go</a></div>"#,
+    /// );
+    /// let div = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     div.html_wrapped(20),
+    ///     "<div><a\n       href=\"/some/rather/long/path\">Go</a></div>"
+    /// );
+    /// ```
+    pub fn html_wrapped(&self, width: usize) -> String {
+        let opts = SerializeOpts {
+            scripting_enabled: false, // It's not clear what this does.
+            traversal_scope: TraversalScope::IncludeNode,
+            create_missing_parent: false,
+        };
+        let mut serializer = serializable::WrappingSerializer::new(opts, width);
+        crate::node::serializable::serialize(**self, &mut serializer, TraversalScope::IncludeNode)
+            .unwrap();
+        serializer.into_string()
+    }
+
+    fn serialize_filtered<F: Fn(&QualName) -> bool>(&self, keep_attr: F) -> String {
+        let opts = SerializeOpts {
+            scripting_enabled: false, // It's not clear what this does.
+            traversal_scope: TraversalScope::IncludeNode,
+            create_missing_parent: false,
+        };
+        let mut buf = Vec::new();
+        let mut serializer = HtmlSerializer::new(&mut buf, opts);
+        crate::node::serializable::serialize_filtered(
+            **self,
+            &mut serializer,
+            TraversalScope::IncludeNode,
+            keep_attr,
+        )
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Returns the HTML of this element, keeping only the listed attributes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<a href="/" onclick="track()" class="nav">Home</a>"#);
+    /// let a = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(a.html_with_attrs(&["href"]), r#"<a href="/">Home</a>"#);
+    /// ```
+    pub fn html_with_attrs(&self, allowed: &[&str]) -> String {
+        self.serialize_filtered(|name| allowed.iter().any(|&attr| name.local.as_ref() == attr))
+    }
+
+    /// Returns the HTML of this element with all attributes removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<a href="x" class="y">t</a>"#);
+    /// let a = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(a.html_structure_only(), "<a>t</a>");
+    /// ```
+    pub fn html_structure_only(&self) -> String {
+        self.serialize_filtered(|_| false)
     }
 
     /// Returns the value of an attribute.
@@ -76,6 +454,114 @@ impl<'a> ElementRef<'a> {
         self.value().attr(attr)
     }
 
+    /// Returns true if both elements have the same name and attributes, ignoring their order
+    /// and the children.
+    pub fn shallow_eq(&self, other: &ElementRef) -> bool {
+        let (a, b) = (self.value(), other.value());
+        a.name == b.name && a.attrs == b.attrs
+    }
+
+    /// Returns true if both elements and their descendants have the same names, attributes,
+    /// text and comments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<a class="x">1</a><a class="x">2</a>"#);
+    /// let mut links = fragment.root_element().child_elements();
+    /// let (first, second) = (links.next().unwrap(), links.next().unwrap());
+    ///
+    /// assert!(first.shallow_eq(&second));
+    /// assert!(!first.structurally_eq(&second));
+    /// ```
+    pub fn structurally_eq(&self, other: &ElementRef) -> bool {
+        fn nodes_eq(a: NodeRef<Node>, b: NodeRef<Node>) -> bool {
+            let values_eq = match (a.value(), b.value()) {
+                (Node::Element(x), Node::Element(y)) => x.name == y.name && x.attrs == y.attrs,
+                (x, y) => x == y,
+            };
+            values_eq
+                && a.children().count() == b.children().count()
+                && a.children().zip(b.children()).all(|(x, y)| nodes_eq(x, y))
+        }
+        nodes_eq(**self, **other)
+    }
+
+    /// Groups the child elements that share a skeleton, the nesting of element names ignoring
+    /// attributes, text and comments.
+    ///
+    /// Only skeletons shared by at least two children form a group. Groups are ordered from
+    /// largest to smallest, so the first one is likely the list of items, and ties keep
+    /// document order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(
+    ///     "<ul><li><a>1</a></li><li><a>2</a></li><li>More</li></ul>",
+    /// );
+    /// let ul = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let groups = ul.repeated_children();
+    /// assert_eq!(groups.len(), 1);
+    /// let items: Vec<String> = groups[0].iter().map(|li| li.text().collect()).collect();
+    /// assert_eq!(items, ["1", "2"]);
+    /// ```
+    pub fn repeated_children(&self) -> Vec<Vec<ElementRef<'a>>> {
+        fn skeleton(element: ElementRef, out: &mut String) {
+            out.push_str(&element.value().name.local);
+            out.push('(');
+            for child in element.child_elements() {
+                skeleton(child, out);
+                out.push(',');
+            }
+            out.push(')');
+        }
+
+        let mut groups: Vec<(String, Vec<ElementRef<'a>>)> = Vec::new();
+        for child in self.child_elements() {
+            let mut key = String::new();
+            skeleton(child, &mut key);
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push(child),
+                None => groups.push((key, vec![child])),
+            }
+        }
+
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .map(|(_, group)| group)
+            .filter(|group| group.len() > 1)
+            .collect();
+        groups.sort_by_key(|group| std::cmp::Reverse(group.len()));
+        groups
+    }
+
+    /// Returns true if this element has every one of the classes.
+    ///
+    /// Classes are compared case-sensitively, as by a `.a.b` selector.
+    pub fn has_all_classes(&self, classes: &[&str]) -> bool {
+        let element = self.value();
+        classes
+            .iter()
+            .all(|class| element.has_class(class, CaseSensitivity::CaseSensitive))
+    }
+
+    /// Returns the value of an attribute, matching its name case-insensitively.
+    ///
+    /// The HTML parser lowercases attribute names on HTML elements, but foreign content such as
+    /// SVG keeps them as written, e.g. `viewBox`. An exact match takes precedence.
+    pub fn attr_ci(&self, attr: &str) -> Option<&'a str> {
+        self.attr(attr).or_else(|| {
+            self.value()
+                .attrs()
+                .find(|(name, _)| name.eq_ignore_ascii_case(attr))
+                .map(|(_, value)| value)
+        })
+    }
+
     /// Returns an iterator over descendent text nodes.
     pub fn text(&self) -> Text<'a> {
         Text {
@@ -83,6 +569,391 @@ impl<'a> ElementRef<'a> {
         }
     }
 
+    /// Writes the descendent text to a writer, as yielded by [`text`](Self::text), without
+    /// collecting it into a string.
+    pub fn write_text<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for text in self.text() {
+            writer.write_all(text.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the value this element represents: the `content` attribute of a `<meta>` element
+    /// and the descendent text of any other element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<meta itemprop="price" content="9.99"><span>y</span>"#);
+    /// let mut elements = fragment.root_element().child_elements();
+    ///
+    /// assert_eq!(elements.next().unwrap().content_or_text().as_deref(), Some("9.99"));
+    /// assert_eq!(elements.next().unwrap().content_or_text().as_deref(), Some("y"));
+    /// ```
+    pub fn content_or_text(&self) -> Option<Cow<'a, str>> {
+        if self.value().name() == "meta" {
+            self.attr("content").map(Cow::Borrowed)
+        } else {
+            Some(self.text_contents())
+        }
+    }
+
+    /// Returns the content of a raw text element such as `<script>` or `<style>` as written in
+    /// the source.
+    ///
+    /// The parser does not decode character references or recognize tags inside these elements,
+    /// so their content is verbatim apart from newlines being normalized to `\n`. Returns
+    /// `None` for other elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<script>if (a < b && c) s = '&amp;';</script>");
+    /// let script = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(script.raw_text(), Some("if (a < b && c) s = '&amp;';"));
+    /// ```
+    pub fn raw_text(&self) -> Option<&'a str> {
+        let element = self.value();
+        let is_raw_text = element.name.ns == ns!(html)
+            && matches!(
+                element.name(),
+                "script" | "style" | "xmp" | "iframe" | "noembed" | "noframes" | "plaintext"
+            );
+        if !is_raw_text {
+            return None;
+        }
+        // Adjacent text is merged while parsing, so there is at most one text node.
+        Some(self.text().next().unwrap_or_default())
+    }
+
+    /// Returns the document fragment holding the contents of a `<template>` element.
+    ///
+    /// The fragment is the only child of the template, so its contents can also be selected
+    /// from the template directly. Returns `None` for other elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{ElementRef, Html};
+    /// let fragment = Html::parse_fragment("<template><div>x</div></template>");
+    /// let template = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let content = template.template_content().unwrap();
+    /// let div = content.children().find_map(ElementRef::wrap).unwrap();
+    /// assert_eq!(div.html(), "<div>x</div>");
+    /// ```
+    pub fn template_content(&self) -> Option<NodeRef<'a, Node>> {
+        if self.value().name.expanded() != expanded_name!(html "template") {
+            return None;
+        }
+        self.first_child()
+            .filter(|child| child.value().is_fragment())
+    }
+
+    /// Borrows the descendent text if it is a single node and concatenates it otherwise.
+    pub(crate) fn text_contents(&self) -> Cow<'a, str> {
+        let mut text = self.text();
+        match (text.next(), text.next()) {
+            (None, _) => Cow::Borrowed(""),
+            (Some(first), None) => Cow::Borrowed(first),
+            (Some(first), Some(second)) => {
+                Cow::Owned([first, second].into_iter().chain(text).collect())
+            }
+        }
+    }
+
+    /// Returns the descendent text with a newline in place of each `<br>` element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<address>1 Main St<br>Springfield</address>");
+    /// let address = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(address.text_with_breaks(), "1 Main St\nSpringfield");
+    /// ```
+    pub fn text_with_breaks(&self) -> String {
+        let mut text = String::new();
+        for edge in self.traverse() {
+            if let Edge::Open(node) = edge {
+                match node.value() {
+                    Node::Text(ref t) => text.push_str(t),
+                    Node::Element(ref e) if e.name() == "br" => text.push('\n'),
+                    _ => {}
+                }
+            }
+        }
+        text
+    }
+
+    /// Returns the text of the sibling nodes following this element up to the next element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment("<p><b>Color:</b> red<br>Size: L</p>");
+    /// let label = fragment.select(&Selector::parse("b").unwrap()).next().unwrap();
+    ///
+    /// assert_eq!(label.text_after_until_element(), " red");
+    /// ```
+    pub fn text_after_until_element(&self) -> String {
+        let mut text = String::new();
+        for sibling in self.next_siblings() {
+            match sibling.value() {
+                Node::Text(ref t) => text.push_str(t),
+                Node::Element(_) => break,
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Returns the `<label>` associated with this form control.
+    ///
+    /// A `<label for>` referencing the control's ID takes precedence over an ancestor `<label>`
+    /// without a `for` attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"<label for="email">Email</label><input id="email">"#);
+    /// let input = fragment.select(&Selector::parse("input").unwrap()).next().unwrap();
+    ///
+    /// assert_eq!(input.label_for().unwrap().inner_html(), "Email");
+    /// ```
+    pub fn label_for(&self) -> Option<ElementRef<'a>> {
+        if let Some(id) = self.value().id().filter(|id| !id.is_empty()) {
+            let root = self.ancestors().last().unwrap_or(self.node);
+            let label = root
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .find(|e| e.value().name() == "label" && e.attr("for") == Some(id));
+            if label.is_some() {
+                return label;
+            }
+        }
+
+        self.ancestors()
+            .filter_map(ElementRef::wrap)
+            .find(|e| e.value().name() == "label" && e.attr("for").is_none())
+    }
+
+    /// Returns the level of a `<h1>` to `<h6>` heading element.
+    pub fn heading_level(&self) -> Option<u8> {
+        match self.value().name() {
+            "h1" => Some(1),
+            "h2" => Some(2),
+            "h3" => Some(3),
+            "h4" => Some(4),
+            "h5" => Some(5),
+            "h6" => Some(6),
+            _ => None,
+        }
+    }
+
+    /// Returns the nearest `<h1>` to `<h6>` heading before this element in document order, with
+    /// its level.
+    ///
+    /// This is the heading of the section the element belongs to. Ancestor headings count, since
+    /// they start before the element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let document = Html::parse_document(r#"
+    ///     <h1>Guide</h1>
+    ///     <h2>Install</h2>
+    ///     <div><p>Run the installer.</p></div>
+    /// "#);
+    /// let p = document.select(&Selector::parse("p").unwrap()).next().unwrap();
+    ///
+    /// let (level, heading) = p.section_heading().unwrap();
+    /// assert_eq!((level, heading.inner_html().as_str()), (2, "Install"));
+    /// ```
+    pub fn section_heading(&self) -> Option<(u8, ElementRef<'a>)> {
+        let mut node = **self;
+        loop {
+            node = match node.prev_sibling() {
+                Some(sibling) => last_descendant(sibling),
+                None => node.parent()?,
+            };
+            if let Some(heading) = ElementRef::wrap(node) {
+                if let Some(level) = heading.heading_level() {
+                    return Some((level, heading));
+                }
+            }
+        }
+    }
+
+    /// Returns a selector such as `li:nth-child(3)` identifying this element among its siblings.
+    ///
+    /// As with `:nth-child`, only element siblings are counted.
+    pub fn nth_child_selector(&self) -> String {
+        let index = self
+            .prev_siblings()
+            .filter(|sibling| sibling.value().is_element())
+            .count()
+            + 1;
+        format!("{}:nth-child({})", self.value().name(), index)
+    }
+
+    /// Returns the index of each node among its sibling nodes on the path from the document to
+    /// this element.
+    ///
+    /// All nodes are counted, including text and comments. The path can be resolved with
+    /// [`Html::at_index_path`](crate::Html::at_index_path).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let document = Html::parse_document("<!DOCTYPE html><p>Hello</p><p>World</p>");
+    /// let p = document.select(&Selector::parse("p + p").unwrap()).next().unwrap();
+    ///
+    /// assert_eq!(p.index_path(), [1, 1, 1]);
+    /// ```
+    pub fn index_path(&self) -> Vec<usize> {
+        let mut path: Vec<_> = std::iter::once(**self)
+            .chain(self.ancestors())
+            .filter(|node| node.parent().is_some())
+            .map(|node| node.prev_siblings().count())
+            .collect();
+        path.reverse();
+        path
+    }
+
+    /// Returns a selector path such as `html > body > div:nth-of-type(2) > p` to this element.
+    ///
+    /// Each step is a child combinator from the root element. `:nth-of-type` is only added
+    /// where the element has siblings of the same type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let document = Html::parse_document("<div></div><div><p>One</p><span></span></div>");
+    /// let p = document.select(&Selector::parse("p").unwrap()).next().unwrap();
+    ///
+    /// assert_eq!(p.css_path(), "html > body > div:nth-of-type(2) > p");
+    /// ```
+    pub fn css_path(&self) -> String {
+        let mut steps: Vec<_> = std::iter::once(*self)
+            .chain(self.ancestors().filter_map(ElementRef::wrap))
+            .map(|element| {
+                let name = &element.value().name;
+                let mut step = String::new();
+                // Writing to a `String` cannot fail.
+                let _ = cssparser::serialize_identifier(&name.local, &mut step);
+
+                let same_type = |sibling: &NodeRef<'a, Node>| {
+                    sibling
+                        .value()
+                        .as_element()
+                        .is_some_and(|e| e.name == *name)
+                };
+                let index = element.prev_siblings().filter(same_type).count() + 1;
+                if index > 1 || element.next_siblings().any(|s| same_type(&s)) {
+                    let _ = write!(step, ":nth-of-type({})", index);
+                }
+                step
+            })
+            .collect();
+        steps.reverse();
+        steps.join(" > ")
+    }
+
+    /// Returns an iterator over the comments inside this element, each paired with its
+    /// enclosing element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<div><!-- marker --></div>");
+    /// let div = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let (parent, comment) = div.comments_with_parents().next().unwrap();
+    /// assert_eq!(parent, div);
+    /// assert_eq!(comment, " marker ");
+    /// ```
+    pub fn comments_with_parents(&self) -> impl Iterator<Item = (ElementRef<'a>, &'a str)> {
+        self.descendants().filter_map(|node| match node.value() {
+            Node::Comment(comment) => {
+                let parent = node.ancestors().find_map(ElementRef::wrap)?;
+                Some((parent, &**comment))
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the non-whitespace text nodes of this element and its descendants, each paired
+    /// with the [`css_path`](Self::css_path) of its parent element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment("<p>Hello <b>world</b></p>");
+    /// let p = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     p.text_nodes_with_paths(),
+    ///     [
+    ///         ("Hello ".to_owned(), "html > p".to_owned()),
+    ///         ("world".to_owned(), "html > p > b".to_owned()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn text_nodes_with_paths(&self) -> Vec<(String, String)> {
+        self.descendants()
+            .filter_map(|node| match node.value() {
+                Node::Text(text) if !text.trim().is_empty() => {
+                    let parent = node.parent().and_then(ElementRef::wrap)?;
+                    Some((text.to_string(), parent.css_path()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns up to `radius` preceding and following sibling elements, both in document order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment("<ul><li>1</li><li>2</li><li id=x>3</li><li>4</li></ul>");
+    /// let li = fragment.select(&Selector::parse("#x").unwrap()).next().unwrap();
+    ///
+    /// let (before, after) = li.surrounding_elements(1);
+    /// assert_eq!(before[0].inner_html(), "2");
+    /// assert_eq!(after[0].inner_html(), "4");
+    /// ```
+    pub fn surrounding_elements(
+        &self,
+        radius: usize,
+    ) -> (Vec<ElementRef<'a>>, Vec<ElementRef<'a>>) {
+        let mut preceding: Vec<_> = self
+            .prev_siblings()
+            .filter_map(ElementRef::wrap)
+            .take(radius)
+            .collect();
+        preceding.reverse();
+        let following = self
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .take(radius)
+            .collect();
+        (preceding, following)
+    }
+
     /// Iterate over all child nodes which are elements
     ///
     /// # Example
@@ -121,6 +992,14 @@ impl<'a> Deref for ElementRef<'a> {
     }
 }
 
+/// Formats the element as its HTML.
+impl<'a> fmt::Display for ElementRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_html(&mut serializable::FmtWriter::new(f))
+            .map_err(|_| fmt::Error)
+    }
+}
+
 /// Iterator over descendent elements matching a selector.
 #[derive(Debug, Clone)]
 pub struct Select<'a, 'b> {
@@ -170,7 +1049,27 @@ impl<'a> Iterator for Text<'a> {
 }
 
 mod element;
+mod key_values;
+mod layout;
+mod markdown;
+mod owned;
+mod picture;
 mod serializable;
+mod table;
+mod trace;
+
+pub use self::layout::LayoutHints;
+pub(crate) use self::layout::{is_display_hidden, is_hidden};
+pub(crate) use self::owned::append_copy;
+pub use self::owned::{OwnedElementData, OwnedNode};
+pub use self::picture::{parse_srcset, PictureSource, SrcsetCandidate};
+
+fn last_descendant(mut node: NodeRef<Node>) -> NodeRef<Node> {
+    while let Some(child) = node.last_child() {
+        node = child;
+    }
+    node
+}
 
 #[cfg(test)]
 mod tests {
@@ -196,4 +1095,626 @@ mod tests {
         let element2 = element1.select(&sel2).next().unwrap();
         assert_eq!(element2.inner_html(), "3");
     }
+
+    #[test]
+    fn text_with_breaks_in_nested_elements() {
+        let fragment = Html::parse_fragment("<p>Line1<br>Line2<b>!<br/></b>Line3</p>");
+        let p = fragment
+            .select(&Selector::parse("p").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(p.text_with_breaks(), "Line1\nLine2!\nLine3");
+    }
+
+    #[test]
+    fn nth_child_selector_counts_element_siblings() {
+        let fragment = Html::parse_fragment("<ul>text<li>1</li><!-- c --><p>2</p> <li>3</li></ul>");
+        let li = fragment
+            .select(&Selector::parse("li").unwrap())
+            .nth(1)
+            .unwrap();
+        let selector = li.nth_child_selector();
+        assert_eq!(selector, "li:nth-child(3)");
+
+        let matched: Vec<_> = fragment
+            .select(&Selector::parse(&selector).unwrap())
+            .collect();
+        assert_eq!(matched, [li]);
+    }
+
+    #[test]
+    fn content_or_text_of_meta_and_span() {
+        let fragment =
+            Html::parse_fragment(r#"<meta content="x"><meta name="y"><span>y<b>z</b></span>"#);
+        let values: Vec<_> = fragment
+            .root_element()
+            .child_elements()
+            .map(|element| element.content_or_text())
+            .collect();
+        assert_eq!(values, [Some("x".into()), None, Some("yz".into())]);
+    }
+
+    #[test]
+    fn select_parents_are_deduplicated() {
+        let fragment = Html::parse_fragment(
+            r#"<ul id="a"><li>1</li><li>2</li></ul><ul id="b"><li>3</li></ul><ul id="c"></ul>"#,
+        );
+        let ids: Vec<_> = fragment
+            .root_element()
+            .select_parents(&Selector::parse("li").unwrap())
+            .map(|parent| parent.value().id().unwrap())
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn text_after_until_element_in_definition_list() {
+        let fragment =
+            Html::parse_fragment("<dl><dt>Key</dt> value <!-- c -->text<dt>Other</dt>rest</dl>");
+        let values: Vec<_> = fragment
+            .select(&Selector::parse("dt").unwrap())
+            .map(|dt| dt.text_after_until_element())
+            .collect();
+        assert_eq!(values, [" value text", "rest"]);
+    }
+
+    #[test]
+    fn html_with_allowed_attrs() {
+        let fragment = Html::parse_fragment(
+            r#"<div style="color: red" class="card"><a href="/x" onclick="f()" style="">x</a></div>"#,
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(
+            div.html_with_attrs(&["href", "class"]),
+            r#"<div class="card"><a href="/x">x</a></div>"#
+        );
+    }
+
+    #[test]
+    fn local_names_are_interned() {
+        let fragment = Html::parse_fragment("<p>1</p><span>2</span><p>3</p>");
+        let names: Vec<_> = fragment
+            .root_element()
+            .child_elements()
+            .map(|element| element.local_name())
+            .collect();
+        assert_eq!(names[0], names[2]);
+        assert_ne!(names[0], names[1]);
+        assert_eq!(*names[1], local_name!("span"));
+    }
+
+    #[test]
+    fn labeled_values_zip_in_order() {
+        let fragment = Html::parse_fragment(
+            r#"<div>
+                <span class=k> K1 </span><span class=v>V<b>1</b></span>
+                <span class=k>K2</span><span class=v>V2</span>
+                <span class=k>K3</span>
+            </div>"#,
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        let pairs = div.labeled_values(
+            &Selector::parse(".k").unwrap(),
+            &Selector::parse(".v").unwrap(),
+        );
+        assert_eq!(
+            pairs,
+            [
+                ("K1".to_owned(), "V1".to_owned()),
+                ("K2".to_owned(), "V2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn display_is_outer_html() {
+        let fragment = Html::parse_fragment(r#"<p class="a">Hello <b>world</b></p>"#);
+        let p = fragment
+            .select(&Selector::parse("p").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(format!("{}", p), p.html());
+
+        let fragment = Html::parse_fragment(r#"<p title="é">ü &amp; 中</p>"#);
+        let p = fragment.root_element().child_elements().next().unwrap();
+        assert_eq!(p.to_string(), r#"<p title="é">ü &amp; 中</p>"#);
+
+        let mut out = String::new();
+        let mut writer = super::serializable::FmtWriter::new(&mut out);
+        let bytes = "a中".as_bytes();
+        std::io::Write::write_all(&mut writer, &bytes[..2]).unwrap();
+        std::io::Write::write_all(&mut writer, &bytes[2..]).unwrap();
+        assert_eq!(out, "a中");
+    }
+
+    #[test]
+    fn label_for_by_id_and_ancestry() {
+        let fragment = Html::parse_fragment(
+            r#"<form>
+                <label for="x">By id</label><input id="x">
+                <label>Wrapped <input name="y"></label>
+                <label for="other">Other <input id="z"></label>
+                <input name="unlabeled">
+            </form>"#,
+        );
+        let labels: Vec<_> = fragment
+            .select(&Selector::parse("input").unwrap())
+            .map(|input| input.label_for().map(|label| label.text().next().unwrap()))
+            .collect();
+        assert_eq!(labels, [Some("By id"), Some("Wrapped "), None, None]);
+    }
+
+    #[test]
+    fn html_structure_only_drops_nested_attrs() {
+        let fragment = Html::parse_fragment(
+            r#"<ul id="menu"><li class="a" data-n="1">One</li><li style="x"><!-- c -->Two</li></ul>"#,
+        );
+        let ul = fragment
+            .select(&Selector::parse("ul").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(
+            ul.html_structure_only(),
+            "<ul><li>One</li><li><!-- c -->Two</li></ul>"
+        );
+    }
+
+    #[test]
+    fn attr_ci_matches_foreign_attributes() {
+        let fragment = Html::parse_fragment(r#"<svg viewBox="0 0 10 10"></svg><p DATA-X="1"></p>"#);
+        let svg = fragment
+            .select(&Selector::parse("svg").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(svg.attr("viewbox"), None);
+        assert_eq!(svg.attr_ci("viewbox"), Some("0 0 10 10"));
+        assert_eq!(svg.attr_ci("VIEWBOX"), Some("0 0 10 10"));
+        assert_eq!(svg.attr_ci("width"), None);
+
+        let p = fragment
+            .select(&Selector::parse("p").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(p.attr_ci("Data-X"), Some("1"));
+    }
+
+    #[test]
+    fn select_last_in_reverse_document_order() {
+        let fragment = Html::parse_fragment(
+            r#"<div><p id="1"><b id="2"></b></p><p id="3"><i><b id="4"></b></i></p>x</div><b id="5"></b>"#,
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        let last = |selector: &str| {
+            div.select_last(&Selector::parse(selector).unwrap())
+                .map(|e| e.value().id().unwrap())
+        };
+
+        assert_eq!(last("b"), Some("4"));
+        assert_eq!(last("p"), Some("3"));
+        assert_eq!(last("p > b"), Some("2"));
+        assert_eq!(last(":scope > p"), Some("3"));
+        assert_eq!(last("div"), None);
+
+        let empty = fragment
+            .select(&Selector::parse("div + b").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(empty.select_last(&Selector::parse("*").unwrap()), None);
+    }
+
+    #[test]
+    fn text_nodes_map_to_css_paths() {
+        let html = Html::parse_document(
+            r#"<main><p>Intro</p><section><p>One</p><p>Two <a href="/">link</a></p></section><svg><text>Label</text></svg></main>"#,
+        );
+        let main = html
+            .select(&Selector::parse("main").unwrap())
+            .next()
+            .unwrap();
+        let paths = main.text_nodes_with_paths();
+        assert_eq!(
+            paths,
+            [
+                ("Intro", "html > body > main > p"),
+                ("One", "html > body > main > section > p:nth-of-type(1)"),
+                ("Two ", "html > body > main > section > p:nth-of-type(2)"),
+                (
+                    "link",
+                    "html > body > main > section > p:nth-of-type(2) > a"
+                ),
+                ("Label", "html > body > main > svg > text"),
+            ]
+            .map(|(text, path)| (text.to_owned(), path.to_owned()))
+        );
+
+        for (text, path) in paths {
+            let selected: Vec<_> = html.select(&Selector::parse(&path).unwrap()).collect();
+            assert_eq!(selected.len(), 1);
+            assert!(selected[0].text().any(|t| t == text));
+        }
+    }
+
+    #[test]
+    fn raw_text_serialization() {
+        let fragment = Html::parse_fragment(
+            "<div><p>a &lt; b</p><script>if (a < b && c > d) x = ']]>';</script><style>a > b {}</style></div>",
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        let script = fragment
+            .select(&Selector::parse("script").unwrap())
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            div.html(),
+            "<div><p>a &lt; b</p><script>if (a < b && c > d) x = ']]>';</script><style>a > b {}</style></div>"
+        );
+        assert_eq!(script.inner_html(), "if (a < b && c > d) x = ']]>';");
+        assert_eq!(
+            div.html_with_cdata(),
+            "<div><p>a &lt; b</p><script><![CDATA[if (a < b && c > d) x = ']]]]><![CDATA[>';]]></script><style><![CDATA[a > b {}]]></style></div>"
+        );
+    }
+
+    #[test]
+    fn surrounding_elements_within_radius() {
+        let fragment = Html::parse_fragment(
+            "<ul><li>1</li> <li>2</li><!-- c --><li>3</li>text<li>4</li><li>5</li><li>6</li></ul>",
+        );
+        let items: Vec<_> = fragment.select(&Selector::parse("li").unwrap()).collect();
+        let around = |index: usize, radius| {
+            let (before, after) = items[index].surrounding_elements(radius);
+            let texts = |elements: Vec<super::ElementRef>| -> Vec<String> {
+                elements.iter().map(|e| e.inner_html()).collect()
+            };
+            (texts(before), texts(after))
+        };
+
+        assert_eq!(
+            around(3, 2),
+            (vec!["2".into(), "3".into()], vec!["5".into(), "6".into()])
+        );
+        assert_eq!(around(0, 2), (vec![], vec!["2".into(), "3".into()]));
+        assert_eq!(
+            around(5, 3),
+            (vec!["3".into(), "4".into(), "5".into()], vec![])
+        );
+        assert_eq!(around(2, 0), (vec![], vec![]));
+    }
+
+    #[test]
+    fn standalone_document_round_trip() {
+        let fragment =
+            Html::parse_fragment(r#"<article class="post"><h1>T</h1><img src="a.png"></article>"#);
+        let article = fragment
+            .select(&Selector::parse("article").unwrap())
+            .next()
+            .unwrap();
+
+        let standalone = Html::parse_document(&article.to_standalone_document(Some("A <b> & c")));
+        #[cfg(feature = "errors")]
+        assert!(standalone.errors.is_empty());
+        let title = standalone
+            .select(&Selector::parse("head > title").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(title.text().next(), Some("A <b> & c"));
+        let body = standalone
+            .select(&Selector::parse("body").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(body.inner_html(), article.html());
+
+        let untitled = Html::parse_document(&article.to_standalone_document(None));
+        assert!(!untitled.exists(&Selector::parse("title").unwrap()));
+    }
+
+    #[test]
+    fn has_all_classes_requires_every_class() {
+        let fragment = Html::parse_fragment(
+            r#"<p class="a b c">1</p><p class="a c">2</p><p class="A B C">3</p>"#,
+        );
+        let matching: Vec<_> = fragment
+            .select(&Selector::parse("p").unwrap())
+            .filter(|p| p.has_all_classes(&["c", "a", "b"]))
+            .map(|p| p.inner_html())
+            .collect();
+        assert_eq!(matching, ["1"]);
+    }
+
+    #[test]
+    fn shallow_and_structural_equality() {
+        let fragment = Html::parse_fragment(
+            r#"<a class="x" href="/">One</a><a href="/" class="x"><b>Two</b></a><a class="x">One</a><a href="/" class="x">One</a>"#,
+        );
+        let links: Vec<_> = fragment.select(&Selector::parse("a").unwrap()).collect();
+
+        assert!(links[0].shallow_eq(&links[1]));
+        assert!(!links[0].structurally_eq(&links[1]));
+        assert!(!links[0].shallow_eq(&links[2]));
+        assert!(links[0].shallow_eq(&links[3]));
+        assert!(links[0].structurally_eq(&links[3]));
+    }
+
+    #[test]
+    fn comments_report_enclosing_element() {
+        let fragment = Html::parse_fragment(
+            "<!-- outside --><div id=a><!-- a --><p id=b>text<!-- b --></p><!--[if IE]>old<![endif]--></div>",
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        let comments: Vec<_> = div
+            .comments_with_parents()
+            .map(|(parent, comment)| (parent.value().id().unwrap(), comment))
+            .collect();
+        assert_eq!(
+            comments,
+            [("a", " a "), ("b", " b "), ("a", "[if IE]>old<![endif]")]
+        );
+    }
+
+    #[test]
+    fn raw_text_is_verbatim() {
+        let html = Html::parse_document(
+            "<head><style>a > b::after { content: '&lt;' }</style></head>\
+             <body><script>\r\nvar s = '<b>&amp;</b>' && x < 1;\n</script><script></script>\
+             <textarea>&amp;</textarea><svg><style>x</style></svg></body>",
+        );
+        let raw: Vec<_> = html
+            .select(&Selector::parse("style, script, textarea").unwrap())
+            .map(|e| e.raw_text())
+            .collect();
+        assert_eq!(
+            raw,
+            [
+                Some("a > b::after { content: '&lt;' }"),
+                Some("\nvar s = '<b>&amp;</b>' && x < 1;\n"),
+                Some(""),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn inherited_attr_from_ancestors() {
+        let fragment = Html::parse_fragment(
+            r#"<section data-ctx="shop" id="s">
+                <div data-ctx="">
+                    <p id="p"><b id="b" data-ctx="cart">x</b><i id="i">y</i></p>
+                </div>
+            </section>"#,
+        );
+        fn inherited<'a>(fragment: &'a Html, id: &str) -> Option<(&'a str, &'a str)> {
+            fragment
+                .select(&Selector::parse(&format!("#{}", id)).unwrap())
+                .next()
+                .unwrap()
+                .inherited_attr("data-ctx")
+                .map(|(value, element)| (value, element.value().id().unwrap()))
+        }
+
+        assert_eq!(inherited(&fragment, "i"), Some(("shop", "s")));
+        assert_eq!(inherited(&fragment, "p"), Some(("shop", "s")));
+        assert_eq!(inherited(&fragment, "b"), Some(("cart", "b")));
+        assert_eq!(fragment.root_element().inherited_attr("data-ctx"), None);
+    }
+
+    #[test]
+    fn closest_containers() {
+        let fragment = Html::parse_fragment(
+            r#"<form id="f"><table id="outer"><tr><td>
+                <table id="inner"><tr><td id="cell"><ol id="l"><li id="item">x</li></ol></td></tr></table>
+            </td></tr></table></form>"#,
+        );
+        let get = |selector: &str| {
+            fragment
+                .select(&Selector::parse(selector).unwrap())
+                .next()
+                .unwrap()
+        };
+        fn id<'a>(element: Option<super::ElementRef<'a>>) -> Option<&'a str> {
+            element.and_then(|e| e.value().id())
+        }
+
+        let cell = get("#cell");
+        assert_eq!(id(cell.closest_table()), Some("inner"));
+        assert_eq!(id(cell.closest_form()), Some("f"));
+        assert_eq!(id(cell.closest_list()), None);
+        assert_eq!(id(get("#item").closest_list()), Some("l"));
+        assert_eq!(id(get("#inner").closest_table()), Some("inner"));
+        assert_eq!(id(get("#f").closest_table()), None);
+    }
+
+    #[test]
+    fn template_content_fragment() {
+        let fragment = Html::parse_fragment(
+            r#"<template id="t"><div class="row">x</div><span>y</span></template><div>z</div>"#,
+        );
+        let template = fragment
+            .select(&Selector::parse("template").unwrap())
+            .next()
+            .unwrap();
+
+        let content = template.template_content().unwrap();
+        assert!(content.value().is_fragment());
+        let children: Vec<_> = content
+            .children()
+            .filter_map(super::ElementRef::wrap)
+            .map(|child| child.html())
+            .collect();
+        assert_eq!(children, [r#"<div class="row">x</div>"#, "<span>y</span>"]);
+        assert_eq!(
+            template.select(&Selector::parse(".row").unwrap()).count(),
+            1
+        );
+
+        let div = fragment.root_element().child_elements().nth(1).unwrap();
+        assert_eq!(div.template_content(), None);
+    }
+
+    #[test]
+    fn section_heading_precedes_in_document_order() {
+        let document = Html::parse_document(
+            r#"<p id="intro">intro</p>
+            <h1>Title</h1>
+            <section>
+                <h2>First</h2>
+                <div><p id="a">a</p></div>
+                <h3>Detail</h3>
+            </section>
+            <div><p id="b">b</p></div>
+            <h2><span id="c">Second</span></h2>"#,
+        );
+        let heading = |id: &str| {
+            let element = document
+                .select(&Selector::parse(&format!("#{}", id)).unwrap())
+                .next()
+                .unwrap();
+            element
+                .section_heading()
+                .map(|(level, heading)| (level, heading.text().collect::<String>()))
+        };
+
+        assert_eq!(heading("intro"), None);
+        assert_eq!(heading("a"), Some((2, "First".to_owned())));
+        assert_eq!(heading("b"), Some((3, "Detail".to_owned())));
+        assert_eq!(heading("c"), Some((2, "Second".to_owned())));
+    }
+
+    #[test]
+    fn write_text_matches_text() {
+        let fragment = Html::parse_fragment(
+            "<article><h1>Title</h1><!-- c --><p>Caf\u{e9} &amp; <b>bar</b></p>\n<p>End</p></article>",
+        );
+        let article = fragment.root_element().child_elements().next().unwrap();
+
+        let mut buf = Vec::new();
+        article.write_text(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            article.text().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn index_path_round_trip() {
+        let document = Html::parse_document(
+            "<!DOCTYPE html><!-- c --><html><head></head><body>\n<div>text<!-- x --><p><b>1</b></p></div></body></html>",
+        );
+        for element in document.root_element().descendent_elements() {
+            let path = element.index_path();
+            assert_eq!(document.at_index_path(&path), Some(*element));
+        }
+
+        let b = document
+            .select(&Selector::parse("b").unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(b.index_path(), [2, 1, 1, 2, 0]);
+        assert_eq!(document.at_index_path(&[]), Some(document.tree.root()));
+        assert_eq!(document.at_index_path(&[2, 1, 1, 9]), None);
+    }
+
+    #[test]
+    fn select_with_preceding_text_pairs() {
+        let fragment = Html::parse_fragment(
+            r#"<div>Price: <span class="v">9.99</span>
+            <br>Stock:<span class="v">3</span><b>!</b><span class="v">none</span>
+            <!-- c --><span class="v">after comment</span></div>"#,
+        );
+        let pairs: Vec<_> = fragment
+            .root_element()
+            .select_with_preceding_text(&Selector::parse(".v").unwrap())
+            .map(|(element, text)| (element.inner_html(), text))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("9.99".to_owned(), Some("Price: ")),
+                ("3".to_owned(), Some("Stock:")),
+                ("none".to_owned(), None),
+                ("after comment".to_owned(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn html_wrapped_breaks_long_start_tags() {
+        let fragment = Html::parse_fragment(concat!(
+            r#"<section id="main" class="content wide" data-analytics-id="section-main-0001">"#,
+            r#"<p class="x">Hi</p></section>"#,
+        ));
+        let section = fragment.root_element().child_elements().next().unwrap();
+
+        let html = section.html_wrapped(40);
+        let mut lines: Vec<_> = html.lines().collect();
+        assert_eq!(lines.remove(0), "<section");
+        let last = lines.pop().unwrap();
+        let (last_attr, rest) = last.split_once('>').unwrap();
+        assert_eq!(rest, r#"<p class="x">Hi</p></section>"#);
+        lines.push(last_attr);
+        lines.sort_unstable();
+        assert_eq!(
+            lines,
+            [
+                r#"  class="content wide""#,
+                r#"  data-analytics-id="section-main-0001""#,
+                r#"  id="main""#,
+            ]
+        );
+
+        assert_eq!(section.html_wrapped(200), section.html());
+    }
+
+    #[test]
+    fn repeated_children_finds_cards() {
+        let fragment = Html::parse_fragment(
+            r#"<div id="list">
+                <div class="banner"><img></div>
+                <div class="card" data-id="1"><h2>A</h2><p>a</p></div>
+                <div class="card" data-id="2"><h2>B</h2><p>b</p></div>
+                <div class="card" data-id="3"><h2>C</h2><p>c</p></div>
+                <div class="divider"></div>
+                <div class="card" data-id="4"><h2>D</h2><p>d</p></div>
+                <div class="card" data-id="5"><h2>E</h2><p>e</p></div>
+            </div>"#,
+        );
+        let list = fragment.root_element().child_elements().next().unwrap();
+
+        let groups = list.repeated_children();
+        assert_eq!(groups.len(), 1);
+        let ids: Vec<_> = groups[0].iter().map(|card| card.attr("data-id")).collect();
+        assert_eq!(ids, [Some("1"), Some("2"), Some("3"), Some("4"), Some("5")]);
+
+        let fragment = Html::parse_fragment("<div><p>1</p><span></span></div>");
+        let div = fragment.root_element().child_elements().next().unwrap();
+        assert!(div.repeated_children().is_empty());
+    }
+
+    #[test]
+    fn exists_is_scoped_to_descendants() {
+        let fragment = Html::parse_fragment("<div><p>1</p></div><span></span>");
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        assert!(div.exists(&Selector::parse("p").unwrap()));
+        assert!(!div.exists(&Selector::parse("span").unwrap()));
+        assert!(!div.exists(&Selector::parse("div").unwrap()));
+    }
 }
@@ -0,0 +1,259 @@
+use ego_tree::{NodeMut, NodeRef};
+use html5ever::{Attribute, LocalName, QualName};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::ElementRef;
+use crate::node::{Comment, Element, Text};
+use crate::{Html, Node};
+
+/// An element and its descendants, detached from the tree and fully owned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OwnedElementData {
+    /// The element name.
+    pub name: String,
+
+    /// The element attributes.
+    pub attrs: Vec<(String, String)>,
+
+    /// The child nodes.
+    pub children: Vec<OwnedNode>,
+}
+
+/// A node of an [`OwnedElementData`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OwnedNode {
+    /// An element.
+    Element(OwnedElementData),
+
+    /// Text.
+    Text(String),
+
+    /// A comment.
+    Comment(String),
+}
+
+impl OwnedElementData {
+    /// Builds a fragment containing this element, as if parsed by [`Html::parse_fragment`].
+    pub fn to_fragment(&self) -> Html {
+        let mut fragment = Html::new_fragment();
+        let mut html = fragment.tree.root_mut();
+        let mut html = html.append(element(&LocalName::from("html"), &[]));
+        self.append_to(&mut html);
+        fragment
+    }
+
+    fn append_to(&self, parent: &mut NodeMut<Node>) {
+        let mut node = parent.append(element(&LocalName::from(&*self.name), &self.attrs));
+        for child in &self.children {
+            match child {
+                OwnedNode::Element(e) => e.append_to(&mut node),
+                OwnedNode::Text(text) => {
+                    node.append(Node::Text(Text {
+                        text: text.as_str().into(),
+                    }));
+                }
+                OwnedNode::Comment(comment) => {
+                    node.append(Node::Comment(Comment {
+                        comment: comment.as_str().into(),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+fn element(name: &LocalName, attrs: &[(String, String)]) -> Node {
+    let attrs = attrs
+        .iter()
+        .map(|(name, value)| Attribute {
+            name: QualName::new(None, ns!(), LocalName::from(&**name)),
+            value: value.as_str().into(),
+        })
+        .collect();
+    Node::Element(Element::new(
+        QualName::new(None, ns!(html), name.clone()),
+        attrs,
+    ))
+}
+
+impl<'a> ElementRef<'a> {
+    /// Copies this element and its descendants into an owned structure.
+    ///
+    /// Only elements, text and comments are copied. Element and attribute namespaces are not
+    /// retained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// use scraper::element_ref::OwnedNode;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<a href="/">Home</a>"#);
+    /// let a = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let owned = a.to_owned_element();
+    /// assert_eq!(owned.name, "a");
+    /// assert_eq!(owned.attrs, [("href".to_owned(), "/".to_owned())]);
+    /// assert_eq!(owned.children, [OwnedNode::Text("Home".to_owned())]);
+    /// ```
+    pub fn to_owned_element(&self) -> OwnedElementData {
+        let element = self.value();
+        OwnedElementData {
+            name: element.name().to_owned(),
+            attrs: element
+                .attrs()
+                .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            children: self
+                .children()
+                .filter_map(|child| match child.value() {
+                    Node::Element(_) => {
+                        ElementRef::wrap(child).map(|e| OwnedNode::Element(e.to_owned_element()))
+                    }
+                    Node::Text(ref text) => Some(OwnedNode::Text(text.to_string())),
+                    Node::Comment(ref comment) => Some(OwnedNode::Comment(comment.to_string())),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Copies this element and its descendants into a new fragment, changing attributes of the
+    /// copied element.
+    ///
+    /// Each change sets an attribute to `Some` value or removes it with `None`. Later changes to
+    /// the same attribute take precedence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"<a href="/old">Home</a>"#);
+    /// let a = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let copy = a.with_attrs(&[("href", Some("/new"))]);
+    /// assert_eq!(copy.root_element().inner_html(), r#"<a href="/new">Home</a>"#);
+    /// ```
+    pub fn with_attrs(&self, changes: &[(&str, Option<&str>)]) -> Html {
+        let is_changed = |name: &QualName| {
+            name.ns == ns!() && changes.iter().any(|(changed, _)| name.local == **changed)
+        };
+        let mut attrs: Vec<_> = self
+            .value()
+            .attrs
+            .iter()
+            .filter(|(name, _)| !is_changed(name))
+            .map(|(name, value)| Attribute {
+                name: name.clone(),
+                value: (**value).into(),
+            })
+            .collect();
+        for (name, value) in changes {
+            attrs.retain(|attr| attr.name.ns != ns!() || attr.name.local != **name);
+            if let Some(value) = value {
+                attrs.push(Attribute {
+                    name: QualName::new(None, ns!(), LocalName::from(*name)),
+                    value: (*value).into(),
+                });
+            }
+        }
+
+        let mut fragment = Html::new_fragment();
+        let mut html = fragment.tree.root_mut();
+        let mut html = html.append(element(&LocalName::from("html"), &[]));
+        let mut copy = html.append(Node::Element(Element::new(
+            self.value().name.clone(),
+            attrs,
+        )));
+        for child in self.children() {
+            append_copy(child, &mut copy);
+        }
+        fragment
+    }
+}
+
+pub(crate) fn append_copy(node: NodeRef<Node>, parent: &mut NodeMut<Node>) {
+    let mut copy = parent.append(node.value().clone());
+    for child in node.children() {
+        append_copy(child, &mut copy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OwnedNode;
+    use crate::{Html, Selector};
+
+    #[test]
+    fn owned_element_fidelity_and_round_trip() {
+        let fragment = Html::parse_fragment(
+            r#"<div class="card"><h2>Title</h2><!-- note --><p>Body <a href="/x">link</a></p></div>"#,
+        );
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+
+        let owned = div.to_owned_element();
+        assert_eq!(owned.name, "div");
+        assert_eq!(owned.attrs, [("class".to_owned(), "card".to_owned())]);
+        assert_eq!(owned.children.len(), 3);
+        assert_eq!(owned.children[1], OwnedNode::Comment(" note ".to_owned()));
+        match &owned.children[2] {
+            OwnedNode::Element(p) => {
+                assert_eq!(p.name, "p");
+                assert_eq!(p.children[0], OwnedNode::Text("Body ".to_owned()));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let rebuilt = owned.to_fragment();
+        let rebuilt_div = rebuilt.root_element().child_elements().next().unwrap();
+        assert_eq!(rebuilt_div.html(), div.html());
+        assert_eq!(rebuilt_div.to_owned_element(), owned);
+    }
+
+    #[test]
+    fn with_attrs_modifies_copy() {
+        let fragment = Html::parse_fragment(
+            r#"<a href="/old" target="_blank"><b>Home</b></a><svg viewBox="0 0 1 1"></svg>"#,
+        );
+        let a = fragment
+            .select(&Selector::parse("a").unwrap())
+            .next()
+            .unwrap();
+
+        let copy = a.with_attrs(&[
+            ("href", Some("/new")),
+            ("target", None),
+            ("rel", Some("x")),
+            ("rel", Some("nofollow")),
+        ]);
+        let copied = copy.root_element().child_elements().next().unwrap();
+        assert_eq!(copied.attr("href"), Some("/new"));
+        assert_eq!(copied.attr("target"), None);
+        assert_eq!(copied.attr("rel"), Some("nofollow"));
+        assert_eq!(copied.inner_html(), "<b>Home</b>");
+        assert_eq!(a.attr("href"), Some("/old"));
+
+        let svg = fragment
+            .select(&Selector::parse("svg").unwrap())
+            .next()
+            .unwrap();
+        let copy = svg.with_attrs(&[]);
+        let copied = copy.root_element().child_elements().next().unwrap();
+        assert_eq!(copied.value().name, svg.value().name);
+        assert_eq!(copied.html(), svg.html());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn owned_element_is_serde() {
+        fn serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        serde::<super::OwnedElementData>();
+    }
+}
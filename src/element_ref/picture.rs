@@ -0,0 +1,207 @@
+use super::ElementRef;
+
+/// A candidate image source of a `<picture>` element.
+///
+/// Produced by [`ElementRef::picture_sources`] for each `<source>` child and for the fallback
+/// `<img>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PictureSource<'a> {
+    /// The `<source>` or `<img>` element.
+    pub element: ElementRef<'a>,
+
+    /// The parsed `srcset` attribute.
+    pub srcset: Vec<SrcsetCandidate<'a>>,
+
+    /// The `media` attribute.
+    pub media: Option<&'a str>,
+
+    /// The `type` attribute.
+    pub mime_type: Option<&'a str>,
+
+    /// The `src` attribute, only present on the fallback `<img>`.
+    pub src: Option<&'a str>,
+}
+
+impl<'a> PictureSource<'a> {
+    fn new(element: ElementRef<'a>) -> Self {
+        PictureSource {
+            element,
+            srcset: element.attr("srcset").map(parse_srcset).unwrap_or_default(),
+            media: element.attr("media"),
+            mime_type: element.attr("type"),
+            src: element.attr("src"),
+        }
+    }
+
+    /// Returns true if this is the fallback `<img>` element.
+    pub fn is_fallback(&self) -> bool {
+        self.element.value().name() == "img"
+    }
+}
+
+/// An image candidate of a `srcset` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SrcsetCandidate<'a> {
+    /// The image URL.
+    pub url: &'a str,
+
+    /// The width (`100w`) or pixel density (`2x`) descriptor.
+    pub descriptor: Option<&'a str>,
+}
+
+/// Parses a `srcset` attribute into its image candidates.
+///
+/// Follows the HTML standard's algorithm: URLs are split on whitespace, a trailing comma ends a
+/// candidate without descriptors and commas inside parentheses do not end the descriptors.
+pub fn parse_srcset(srcset: &str) -> Vec<SrcsetCandidate<'_>> {
+    let mut candidates = Vec::new();
+    let mut rest = srcset;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+
+        let url_end = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let url = &rest[..url_end];
+        rest = &rest[url_end..];
+
+        if url.ends_with(',') {
+            candidates.push(SrcsetCandidate {
+                url: url.trim_end_matches(','),
+                descriptor: None,
+            });
+            continue;
+        }
+
+        let mut depth = 0usize;
+        let mut descriptor_end = rest.len();
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    descriptor_end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let descriptor = rest[..descriptor_end].trim();
+        rest = &rest[descriptor_end..];
+
+        candidates.push(SrcsetCandidate {
+            url,
+            descriptor: (!descriptor.is_empty()).then_some(descriptor),
+        });
+    }
+
+    candidates
+}
+
+impl<'a> ElementRef<'a> {
+    /// Returns the candidate sources of a `<picture>` element.
+    ///
+    /// Each `<source>` child is returned in document order, followed by the fallback `<img>`.
+    /// Returns an empty vector if this is not a `<picture>` element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment(r#"
+    ///     <picture>
+    ///         <source srcset="wide.webp 1200w, narrow.webp 600w" media="(min-width: 800px)" type="image/webp">
+    ///         <img src="fallback.jpg" alt="">
+    ///     </picture>
+    /// "#);
+    /// let picture = fragment.select(&Selector::parse("picture").unwrap()).next().unwrap();
+    ///
+    /// let sources = picture.picture_sources();
+    /// assert_eq!(sources[0].srcset[1].url, "narrow.webp");
+    /// assert_eq!(sources[0].media, Some("(min-width: 800px)"));
+    /// assert_eq!(sources[1].src, Some("fallback.jpg"));
+    /// ```
+    pub fn picture_sources(&self) -> Vec<PictureSource<'a>> {
+        if self.value().name() != "picture" {
+            return Vec::new();
+        }
+
+        self.child_elements()
+            .filter(|child| matches!(child.value().name(), "source" | "img"))
+            .map(PictureSource::new)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_srcset, SrcsetCandidate};
+    use crate::html::Html;
+    use crate::selector::Selector;
+
+    #[test]
+    fn picture_with_sources_and_fallback() {
+        let fragment = Html::parse_fragment(
+            r#"<picture>
+                <source srcset="a.avif" type="image/avif">
+                <source srcset="b-1x.jpg, b-2x.jpg 2x" media="(min-width: 600px)">
+                <img src="c.jpg" srcset="c-2x.jpg 2x">
+            </picture>"#,
+        );
+        let picture = fragment
+            .select(&Selector::parse("picture").unwrap())
+            .next()
+            .unwrap();
+
+        let sources = picture.picture_sources();
+        assert_eq!(sources.len(), 3);
+
+        assert_eq!(sources[0].mime_type, Some("image/avif"));
+        assert_eq!(sources[0].media, None);
+        assert!(!sources[0].is_fallback());
+
+        assert_eq!(sources[1].media, Some("(min-width: 600px)"));
+        assert_eq!(sources[1].srcset.len(), 2);
+        assert_eq!(sources[1].srcset[1].descriptor, Some("2x"));
+
+        assert!(sources[2].is_fallback());
+        assert_eq!(sources[2].src, Some("c.jpg"));
+        assert_eq!(sources[2].srcset[0].url, "c-2x.jpg");
+    }
+
+    #[test]
+    fn picture_sources_of_other_element() {
+        let fragment = Html::parse_fragment(r#"<div><img src="a.jpg"></div>"#);
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+        assert!(div.picture_sources().is_empty());
+    }
+
+    #[test]
+    fn srcset_grammar() {
+        assert_eq!(
+            parse_srcset(" a.jpg  1x ,b.jpg,, c.jpg 100w (x, y)"),
+            [
+                SrcsetCandidate {
+                    url: "a.jpg",
+                    descriptor: Some("1x"),
+                },
+                SrcsetCandidate {
+                    url: "b.jpg",
+                    descriptor: None,
+                },
+                SrcsetCandidate {
+                    url: "c.jpg",
+                    descriptor: Some("100w (x, y)"),
+                },
+            ]
+        );
+        assert!(parse_srcset("  ").is_empty());
+    }
+}
@@ -1,6 +1,10 @@
-use std::io::Error;
+use std::fmt;
+use std::io::{self, Error, ErrorKind, Write};
 
-use html5ever::serialize::{Serialize, Serializer, TraversalScope};
+use html5ever::serialize::{
+    AttrRef, HtmlSerializer, Serialize, SerializeOpts, Serializer, TraversalScope,
+};
+use html5ever::QualName;
 
 use crate::ElementRef;
 
@@ -13,3 +17,184 @@ impl<'a> Serialize for ElementRef<'a> {
         crate::node::serializable::serialize(**self, serializer, traversal_scope)
     }
 }
+
+/// An HTML serializer wrapping the raw text of `<script>` and `<style>` elements in CDATA
+/// sections.
+pub(super) struct CdataSerializer<W: Write> {
+    inner: HtmlSerializer<W>,
+    raw_text: Vec<bool>,
+}
+
+impl<W: Write> CdataSerializer<W> {
+    pub(super) fn new(writer: W, opts: SerializeOpts) -> Self {
+        CdataSerializer {
+            inner: HtmlSerializer::new(writer, opts),
+            raw_text: Vec::new(),
+        }
+    }
+}
+
+impl<W: Write> Serializer for CdataSerializer<W> {
+    fn start_elem<'a, AttrIter>(&mut self, name: QualName, attrs: AttrIter) -> Result<(), Error>
+    where
+        AttrIter: Iterator<Item = AttrRef<'a>>,
+    {
+        self.raw_text
+            .push(name.ns == ns!(html) && matches!(&*name.local, "script" | "style"));
+        self.inner.start_elem(name, attrs)
+    }
+
+    fn end_elem(&mut self, name: QualName) -> Result<(), Error> {
+        self.raw_text.pop();
+        self.inner.end_elem(name)
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), Error> {
+        if self.raw_text.last() == Some(&true) {
+            let writer = &mut self.inner.writer;
+            writer.write_all(b"<![CDATA[")?;
+            // A CDATA section cannot contain its end marker, so split it across two sections.
+            writer.write_all(text.replace("]]>", "]]]]><![CDATA[>").as_bytes())?;
+            writer.write_all(b"]]>")
+        } else {
+            self.inner.write_text(text)
+        }
+    }
+
+    fn write_comment(&mut self, text: &str) -> Result<(), Error> {
+        self.inner.write_comment(text)
+    }
+
+    fn write_doctype(&mut self, name: &str) -> Result<(), Error> {
+        self.inner.write_doctype(name)
+    }
+
+    fn write_processing_instruction(&mut self, target: &str, data: &str) -> Result<(), Error> {
+        self.inner.write_processing_instruction(target, data)
+    }
+}
+
+/// An HTML serializer that puts each attribute of a start tag on its own line when the tag
+/// would otherwise be longer than a given width.
+pub(super) struct WrappingSerializer {
+    inner: HtmlSerializer<Vec<u8>>,
+    opts: SerializeOpts,
+    width: usize,
+}
+
+impl WrappingSerializer {
+    pub(super) fn new(opts: SerializeOpts, width: usize) -> Self {
+        WrappingSerializer {
+            inner: HtmlSerializer::new(Vec::new(), opts.clone()),
+            opts,
+            width,
+        }
+    }
+
+    pub(super) fn into_string(self) -> String {
+        String::from_utf8(self.inner.writer).unwrap()
+    }
+
+    /// Serializes a single attribute, including its leading space.
+    fn attr(&self, name: &QualName, attr: AttrRef) -> Result<String, Error> {
+        let mut scratch = HtmlSerializer::new(Vec::new(), self.opts.clone());
+        scratch.start_elem(name.clone(), std::iter::once(attr))?;
+        let tag = String::from_utf8(scratch.writer).unwrap();
+        Ok(tag[1 + name.local.len()..tag.len() - 1].to_owned())
+    }
+}
+
+impl Serializer for WrappingSerializer {
+    fn start_elem<'a, AttrIter>(&mut self, name: QualName, attrs: AttrIter) -> Result<(), Error>
+    where
+        AttrIter: Iterator<Item = AttrRef<'a>>,
+    {
+        let attrs: Vec<_> = attrs.collect();
+        let start = self.inner.writer.len();
+        // The inner serializer tracks open elements, so it always writes the tag first.
+        self.inner.start_elem(name.clone(), attrs.iter().copied())?;
+
+        let tag = std::str::from_utf8(&self.inner.writer[start..]).unwrap();
+        if attrs.is_empty() || tag.chars().count() <= self.width {
+            return Ok(());
+        }
+
+        let line_start = self.inner.writer[..start]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let column = std::str::from_utf8(&self.inner.writer[line_start..start])
+            .unwrap()
+            .chars()
+            .count();
+        let indent = " ".repeat(column + 2);
+
+        let mut wrapped = format!("<{}", name.local);
+        for &attr in &attrs {
+            wrapped.push('\n');
+            wrapped.push_str(&indent);
+            wrapped.push_str(self.attr(&name, attr)?.trim_start());
+        }
+        wrapped.push('>');
+
+        self.inner.writer.truncate(start);
+        self.inner.writer.extend_from_slice(wrapped.as_bytes());
+        Ok(())
+    }
+
+    fn end_elem(&mut self, name: QualName) -> Result<(), Error> {
+        self.inner.end_elem(name)
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), Error> {
+        self.inner.write_text(text)
+    }
+
+    fn write_comment(&mut self, text: &str) -> Result<(), Error> {
+        self.inner.write_comment(text)
+    }
+
+    fn write_doctype(&mut self, name: &str) -> Result<(), Error> {
+        self.inner.write_doctype(name)
+    }
+
+    fn write_processing_instruction(&mut self, target: &str, data: &str) -> Result<(), Error> {
+        self.inner.write_processing_instruction(target, data)
+    }
+}
+
+/// An `io::Write` adapter writing UTF-8 into a `fmt::Write`.
+///
+/// A character split across calls to `write` is held back until its remaining bytes arrive.
+pub(super) struct FmtWriter<'f, F: fmt::Write> {
+    inner: &'f mut F,
+    pending: Vec<u8>,
+}
+
+impl<'f, F: fmt::Write> FmtWriter<'f, F> {
+    pub(super) fn new(inner: &'f mut F) -> Self {
+        FmtWriter {
+            inner,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<F: fmt::Write> Write for FmtWriter<'_, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
+        };
+        let s = std::str::from_utf8(&self.pending[..valid]).unwrap();
+        self.inner.write_str(s).map_err(Error::other)?;
+        self.pending.drain(..valid);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
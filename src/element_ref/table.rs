@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use super::ElementRef;
+
+impl<'a> ElementRef<'a> {
+    /// Returns the `<tr>` rows of this `<table>`, excluding those of nested tables.
+    fn table_row_elements(&self) -> impl Iterator<Item = ElementRef<'a>> + '_ {
+        self.descendent_elements().filter(move |row| {
+            row.value().name() == "tr"
+                && row
+                    .ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|ancestor| ancestor.value().name() == "table")
+                    == Some(*self)
+        })
+    }
+
+    /// Returns the trimmed text of each `<th>` and `<td>` cell of each row of this `<table>`.
+    ///
+    /// Rows of nested tables are excluded.
+    pub fn table_rows(&self) -> Vec<Vec<String>> {
+        self.table_row_elements()
+            .map(|row| {
+                row.child_elements()
+                    .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                    .map(|cell| cell.text().collect::<String>().trim().to_owned())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the rows of this `<table>` as records keyed by the header cells.
+    ///
+    /// The header is the first row of the `<thead>` if there is one, and the first row
+    /// otherwise. Cells beyond the header are keyed by their zero-based column index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::Html;
+    /// let fragment = Html::parse_fragment(r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>Ada</td><td>36</td></tr>
+    ///     </table>
+    /// "#);
+    /// let table = fragment.root_element().child_elements().next().unwrap();
+    ///
+    /// let records = table.table_records();
+    /// assert_eq!(records[0]["Name"], "Ada");
+    /// assert_eq!(records[0]["Age"], "36");
+    /// ```
+    pub fn table_records(&self) -> Vec<HashMap<String, String>> {
+        let header_index = self
+            .table_row_elements()
+            .position(|row| {
+                row.parent()
+                    .and_then(ElementRef::wrap)
+                    .map(|p| p.value().name())
+                    == Some("thead")
+            })
+            .unwrap_or(0);
+
+        let mut rows = self.table_rows();
+        if rows.len() <= header_index {
+            return Vec::new();
+        }
+        let header = rows.remove(header_index);
+
+        rows.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(i, cell)| {
+                        let key = header.get(i).cloned().unwrap_or_else(|| i.to_string());
+                        (key, cell)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{Html, Selector};
+
+    fn record(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn table_records_with_thead() {
+        let fragment = Html::parse_fragment(
+            r#"<table id="t">
+                <caption>People</caption>
+                <thead><tr><th> Name </th><th>Age</th></tr></thead>
+                <tbody>
+                    <tr><td>Ada</td><td>36</td></tr>
+                    <tr><td>Bob</td><td>7</td><td>extra</td></tr>
+                    <tr><td>Cy<table><tr><td>nested</td></tr></table></td></tr>
+                </tbody>
+            </table>"#,
+        );
+        let table = fragment
+            .select(&Selector::parse("#t").unwrap())
+            .next()
+            .unwrap();
+
+        assert_eq!(table.table_rows().len(), 4);
+        assert_eq!(
+            table.table_records(),
+            [
+                record(&[("Name", "Ada"), ("Age", "36")]),
+                record(&[("Name", "Bob"), ("Age", "7"), ("2", "extra")]),
+                record(&[("Name", "Cynested")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn table_records_with_header_row() {
+        let fragment = Html::parse_fragment(
+            "<table><tr><td>k</td></tr><tr><td>v</td></tr></table><table></table>",
+        );
+        let tables: Vec<_> = fragment
+            .select(&Selector::parse("table").unwrap())
+            .collect();
+        assert_eq!(tables[0].table_records(), [record(&[("k", "v")])]);
+        assert!(tables[1].table_records().is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use selectors::parser::Combinator;
+use selectors::Element;
+
+use super::ElementRef;
+use crate::selector::Compound;
+use crate::Selector;
+
+impl<'a> ElementRef<'a> {
+    /// Traces how a selector matches this element through its ancestors.
+    ///
+    /// Returns each ancestor element, from the parent up to the root, paired with whether it
+    /// satisfied one of the compound selectors to the left of a child or descendant combinator.
+    /// The first selector of the group which matches is traced, backtracking as the selector
+    /// engine does. If the element does not match, no ancestor is marked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scraper::{Html, Selector};
+    /// let fragment = Html::parse_fragment("<div><ul><li><b>x</b></li></ul></div>");
+    /// let b = fragment.select(&Selector::parse("b").unwrap()).next().unwrap();
+    ///
+    /// let trace: Vec<_> = b
+    ///     .ancestor_match_trace(&Selector::parse("div > ul b").unwrap())
+    ///     .into_iter()
+    ///     .map(|(ancestor, matched)| (ancestor.value().name(), matched))
+    ///     .collect();
+    /// assert_eq!(
+    ///     trace,
+    ///     [("li", false), ("ul", true), ("div", true), ("html", false)]
+    /// );
+    /// ```
+    pub fn ancestor_match_trace(&self, selector: &Selector) -> Vec<(ElementRef<'a>, bool)> {
+        let ancestors: Vec<_> = self.ancestors().filter_map(ElementRef::wrap).collect();
+
+        let mut matched = Vec::new();
+        for chain in selector.compound_chains() {
+            if chain[0].0.matches(self) && match_chain(&chain, *self, 0, &ancestors, &mut matched) {
+                break;
+            }
+            matched.clear();
+        }
+
+        ancestors
+            .iter()
+            .enumerate()
+            .map(|(i, ancestor)| (*ancestor, matched.contains(&i)))
+            .collect()
+    }
+}
+
+/// Matches the rest of a chain given that `element`, whose parent is `ancestors[depth]`,
+/// matched the first compound of `chain`. Records the indices of the ancestors used.
+fn match_chain<'a>(
+    chain: &[(Compound, Option<Combinator>)],
+    element: ElementRef<'a>,
+    depth: usize,
+    ancestors: &[ElementRef<'a>],
+    matched: &mut Vec<usize>,
+) -> bool {
+    let (_, combinator) = &chain[0];
+    let rest = &chain[1..];
+    let Some(combinator) = combinator else {
+        return true;
+    };
+    let compound = &rest[0].0;
+
+    let try_ancestor = |i: usize, matched: &mut Vec<usize>| {
+        let ancestor = ancestors[i];
+        if compound.matches(&ancestor) && match_chain(rest, ancestor, i + 1, ancestors, matched) {
+            matched.push(i);
+            true
+        } else {
+            false
+        }
+    };
+
+    match combinator {
+        Combinator::Child => depth < ancestors.len() && try_ancestor(depth, matched),
+        Combinator::Descendant => (depth..ancestors.len()).any(|i| try_ancestor(i, matched)),
+        Combinator::NextSibling => element.prev_sibling_element().is_some_and(|sibling| {
+            compound.matches(&sibling) && match_chain(rest, sibling, depth, ancestors, matched)
+        }),
+        Combinator::LaterSibling => {
+            let mut sibling = element.prev_sibling_element();
+            while let Some(s) = sibling {
+                if compound.matches(&s) && match_chain(rest, s, depth, ancestors, matched) {
+                    return true;
+                }
+                sibling = s.prev_sibling_element();
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Html, Selector};
+
+    #[test]
+    fn trace_backtracks_to_matching_ancestors() {
+        let fragment = Html::parse_fragment(
+            r#"<section><div><ul id="a"><li><span><ul id="b"><li id="t">x</li></ul></span></li></ul></div></section>"#,
+        );
+        let target = fragment
+            .select(&Selector::parse("#t").unwrap())
+            .next()
+            .unwrap();
+        let trace = |selector: &str| {
+            target
+                .ancestor_match_trace(&Selector::parse(selector).unwrap())
+                .into_iter()
+                .map(|(ancestor, matched)| {
+                    (
+                        ancestor.value().id().unwrap_or(ancestor.value().name()),
+                        matched,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            trace("div > ul li"),
+            [
+                ("b", false),
+                ("span", false),
+                ("li", false),
+                ("a", true),
+                ("div", true),
+                ("section", false),
+                ("html", false),
+            ]
+        );
+        assert_eq!(
+            trace("em, section ul > li"),
+            [
+                ("b", true),
+                ("span", false),
+                ("li", false),
+                ("a", false),
+                ("div", false),
+                ("section", true),
+                ("html", false),
+            ]
+        );
+        assert!(trace("article li").iter().all(|(_, matched)| !matched));
+    }
+
+    #[test]
+    fn trace_matches_attribute_values() {
+        let fragment = Html::parse_fragment(
+            r#"<div class="x" lang="en-GB"><p title="a b"><b>x</b></p></div>"#,
+        );
+        let b = fragment
+            .select(&Selector::parse("b").unwrap())
+            .next()
+            .unwrap();
+        let trace = |selector: &str| {
+            b.ancestor_match_trace(&Selector::parse(selector).unwrap())
+                .into_iter()
+                .map(|(ancestor, matched)| (ancestor.value().name(), matched))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            trace(r#"[class="x"] b"#),
+            [("p", false), ("div", true), ("html", false)]
+        );
+        assert_eq!(
+            trace(r#"[lang|="en"] > [title~="b"] > b"#),
+            [("p", true), ("div", true), ("html", false)]
+        );
+        assert!(trace(r#"[class^="y"] b"#)
+            .iter()
+            .all(|(_, matched)| !matched));
+    }
+}
@@ -8,6 +8,8 @@ use std::{error::Error, fmt::Display};
 use cssparser::{BasicParseErrorKind, ParseErrorKind, Token};
 use selectors::parser::SelectorParseErrorKind;
 
+use crate::selector::Specificity;
+
 /// Error type that is returned when calling `Selector::parse`
 #[derive(Debug, Clone)]
 pub enum SelectorErrorKind<'a> {
@@ -117,3 +119,42 @@ impl<'a> Error for SelectorErrorKind<'a> {
         }
     }
 }
+
+/// Error type that is returned when calling `Selector::parse_max_specificity`
+#[derive(Debug, Clone)]
+pub enum MaxSpecificityError<'a> {
+    /// The selector failed to parse
+    Invalid(SelectorErrorKind<'a>),
+
+    /// A selector exceeds the maximum allowed specificity
+    TooSpecific(Specificity),
+}
+
+impl<'a> From<SelectorErrorKind<'a>> for MaxSpecificityError<'a> {
+    fn from(err: SelectorErrorKind<'a>) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+impl<'a> Display for MaxSpecificityError<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(err) => err.fmt(f),
+            Self::TooSpecific(specificity) => write!(
+                f,
+                "Specificity ({}, {}, {}) exceeds the maximum allowed",
+                specificity.ids, specificity.classes, specificity.elements
+            ),
+        }
+    }
+}
+
+impl<'a> Error for MaxSpecificityError<'a> {
+    fn description(&self) -> &str {
+        match self {
+            #[allow(deprecated)]
+            Self::Invalid(err) => err.description(),
+            Self::TooSpecific(_) => "Selector is too specific",
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static ARIA_NAV: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("nav[aria-label*=breadcrumb i]").unwrap());
+static CLASS_LIST: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("ol.breadcrumb, ul.breadcrumb").unwrap());
+static MICRODATA: Lazy<Selector> =
+    Lazy::new(|| Selector::parse(r#"[itemtype$="schema.org/BreadcrumbList"]"#).unwrap());
+static MICRODATA_ITEM: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("[itemprop~=itemListElement]").unwrap());
+static MICRODATA_NAME: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemprop~=name]").unwrap());
+static MICRODATA_URL: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemprop~=item]").unwrap());
+static LIST_ITEM: Lazy<Selector> = Lazy::new(|| Selector::parse("li").unwrap());
+static LINK: Lazy<Selector> = Lazy::new(|| Selector::parse("a[href]").unwrap());
+
+impl Html {
+    /// Returns the breadcrumb trail of the page as `(label, href)` pairs.
+    ///
+    /// The first of these sources yielding any breadcrumb is used:
+    ///
+    /// 1. a `<nav>` whose `aria-label` contains "breadcrumb",
+    /// 2. an `<ol>` or `<ul>` with the `breadcrumb` class,
+    /// 3. a schema.org `BreadcrumbList` in microdata,
+    /// 4. a `BreadcrumbList` in JSON-LD, with the `serde_json` feature.
+    ///
+    /// For the markup sources, each list item, or each link if there are none, is a breadcrumb.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <ol class="breadcrumb"><li><a href="/">Home</a></li><li>Docs</li></ol>
+    /// "#);
+    /// assert_eq!(
+    ///     document.breadcrumbs(),
+    ///     [("Home".to_owned(), Some("/".to_owned())), ("Docs".to_owned(), None)]
+    /// );
+    /// ```
+    pub fn breadcrumbs(&self) -> Vec<(String, Option<String>)> {
+        let from_markup = self
+            .select(&ARIA_NAV)
+            .chain(self.select(&CLASS_LIST))
+            .map(markup_breadcrumbs)
+            .find(|trail| !trail.is_empty());
+        if let Some(trail) = from_markup {
+            return trail;
+        }
+
+        let from_microdata = self
+            .select(&MICRODATA)
+            .map(microdata_breadcrumbs)
+            .find(|trail| !trail.is_empty());
+        if let Some(trail) = from_microdata {
+            return trail;
+        }
+
+        #[cfg(feature = "serde_json")]
+        if let Some(trail) = self.json_ld_breadcrumbs() {
+            return trail;
+        }
+
+        Vec::new()
+    }
+
+    #[cfg(feature = "serde_json")]
+    fn json_ld_breadcrumbs(&self) -> Option<Vec<(String, Option<String>)>> {
+        self.scripts()
+            .filter(|script| {
+                script
+                    .script_type
+                    .is_some_and(|t| t.trim().eq_ignore_ascii_case("application/ld+json"))
+            })
+            .filter_map(|script| serde_json::from_str(script.inline?).ok())
+            .find_map(|value| json_ld::breadcrumbs(&value))
+    }
+}
+
+fn normalized_text(element: ElementRef) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn markup_breadcrumbs(container: ElementRef) -> Vec<(String, Option<String>)> {
+    let link = |element: ElementRef| {
+        element
+            .select(&LINK)
+            .next()
+            .and_then(|a| a.attr("href"))
+            .map(str::to_owned)
+    };
+
+    let mut items: Vec<_> = container.select(&LIST_ITEM).collect();
+    if items.is_empty() {
+        items = container.select(&LINK).collect();
+        return items
+            .into_iter()
+            .map(|a| (normalized_text(a), a.attr("href").map(str::to_owned)))
+            .filter(|(label, _)| !label.is_empty())
+            .collect();
+    }
+
+    items
+        .into_iter()
+        .map(|item| (normalized_text(item), link(item)))
+        .filter(|(label, _)| !label.is_empty())
+        .collect()
+}
+
+fn microdata_breadcrumbs(list: ElementRef) -> Vec<(String, Option<String>)> {
+    list.select(&MICRODATA_ITEM)
+        .filter_map(|item| {
+            let name = item.select(&MICRODATA_NAME).next()?;
+            let label = match name.attr("content") {
+                Some(content) => content.trim().to_owned(),
+                None => normalized_text(name),
+            };
+            let href = std::iter::once(item)
+                .chain(item.select(&MICRODATA_URL))
+                .find_map(|url| url.attr("href").or_else(|| url.attr("itemid")))
+                .map(str::to_owned);
+            Some((label, href)).filter(|(label, _)| !label.is_empty())
+        })
+        .collect()
+}
+
+#[cfg(feature = "serde_json")]
+mod json_ld {
+    use serde_json::Value;
+
+    /// Finds the first `BreadcrumbList` in a JSON-LD value, including `@graph` arrays.
+    pub(super) fn breadcrumbs(value: &Value) -> Option<Vec<(String, Option<String>)>> {
+        match value {
+            Value::Array(values) => values.iter().find_map(breadcrumbs),
+            Value::Object(object) => {
+                if has_type(value, "BreadcrumbList") {
+                    let mut items: Vec<_> = object
+                        .get("itemListElement")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(list_item)
+                        .collect();
+                    // `sort_by_key` is stable, keeping the order of items without positions.
+                    items.sort_by_key(|(position, _)| *position);
+                    let items: Vec<_> = items.into_iter().map(|(_, item)| item).collect();
+                    Some(items).filter(|items| !items.is_empty())
+                } else {
+                    object.get("@graph").and_then(breadcrumbs)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn has_type(value: &Value, name: &str) -> bool {
+        let matches = |t: &Value| {
+            t.as_str()
+                .is_some_and(|t| t == name || t.ends_with(&format!("/{}", name)))
+        };
+        match value.get("@type") {
+            Some(Value::Array(types)) => types.iter().any(matches),
+            Some(t) => matches(t),
+            None => false,
+        }
+    }
+
+    fn list_item(value: &Value) -> Option<(u64, (String, Option<String>))> {
+        let position = value
+            .get("position")
+            .and_then(|p| p.as_u64().or_else(|| p.as_str()?.trim().parse().ok()))
+            .unwrap_or(u64::MAX);
+        let item = value.get("item");
+        let name = value
+            .get("name")
+            .or_else(|| item?.get("name"))
+            .and_then(Value::as_str)?
+            .trim()
+            .to_owned();
+        let href = item
+            .and_then(|item| {
+                item.as_str()
+                    .or_else(|| item.get("@id")?.as_str())
+                    .or_else(|| item.get("url")?.as_str())
+            })
+            .map(str::to_owned);
+        Some((position, (name, href))).filter(|(_, (name, _))| !name.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    fn trail(items: &[(&str, Option<&str>)]) -> Vec<(String, Option<String>)> {
+        items
+            .iter()
+            .map(|(label, href)| (label.to_string(), href.map(str::to_owned)))
+            .collect()
+    }
+
+    #[test]
+    fn breadcrumb_class_list() {
+        let html = Html::parse_document(
+            r#"<ol class="breadcrumb">
+                <li><a href="/">Home</a></li>
+                <li><a href="/docs"> Docs
+                    &amp; guides</a></li>
+                <li class="active">Selectors</li>
+            </ol>"#,
+        );
+        assert_eq!(
+            html.breadcrumbs(),
+            trail(&[
+                ("Home", Some("/")),
+                ("Docs & guides", Some("/docs")),
+                ("Selectors", None),
+            ])
+        );
+    }
+
+    #[test]
+    fn aria_nav_takes_precedence() {
+        let html = Html::parse_document(
+            r#"<ol class="breadcrumb"><li>Ignored</li></ol>
+            <nav aria-label="Breadcrumb"><a href="/">Home</a> › <a href="/a">A</a></nav>"#,
+        );
+        assert_eq!(
+            html.breadcrumbs(),
+            trail(&[("Home", Some("/")), ("A", Some("/a"))])
+        );
+    }
+
+    #[test]
+    fn microdata_breadcrumbs() {
+        let html = Html::parse_document(
+            r#"<ol itemscope itemtype="https://schema.org/BreadcrumbList">
+                <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                    <a itemprop="item" href="/books"><span itemprop="name">Books</span></a>
+                </li>
+                <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                    <span itemprop="name">Fiction</span>
+                </li>
+            </ol>"#,
+        );
+        assert_eq!(
+            html.breadcrumbs(),
+            trail(&[("Books", Some("/books")), ("Fiction", None)])
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_ld_breadcrumb_list() {
+        let html = Html::parse_document(
+            r#"<script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebPage", "name": "Page"},
+                {"@type": "BreadcrumbList", "itemListElement": [
+                    {"@type": "ListItem", "position": 2, "name": "Fiction",
+                     "item": {"@id": "https://example.com/fiction"}},
+                    {"@type": "ListItem", "position": 1, "name": "Books",
+                     "item": "https://example.com/books"},
+                    {"@type": "ListItem", "position": 3, "item": {"name": "Novel"}}
+                ]}
+            ]}
+            </script>"#,
+        );
+        assert_eq!(
+            html.breadcrumbs(),
+            trail(&[
+                ("Books", Some("https://example.com/books")),
+                ("Fiction", Some("https://example.com/fiction")),
+                ("Novel", None),
+            ])
+        );
+    }
+
+    #[test]
+    fn no_breadcrumbs() {
+        let html = Html::parse_document(r#"<nav><a href="/">Home</a></nav>"#);
+        assert!(html.breadcrumbs().is_empty());
+    }
+}
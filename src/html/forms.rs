@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static FIELD: Lazy<Selector> = Lazy::new(|| Selector::parse("input, select, textarea").unwrap());
+static OPTION: Lazy<Selector> = Lazy::new(|| Selector::parse("option").unwrap());
+
+/// An `<input>`, `<select>` or `<textarea>` of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputField<'a> {
+    /// The form the field belongs to.
+    pub form: Option<ElementRef<'a>>,
+
+    /// The `name` attribute.
+    pub name: Option<&'a str>,
+
+    /// The type of the field: the lowercased `type` attribute of an `<input>`, defaulting to
+    /// `text` if missing or invalid, or `select`, `select-multiple` or `textarea`.
+    pub field_type: &'a str,
+
+    /// The initial value: the `value` attribute of an `<input>`, the value of the selected or
+    /// first `<option>` of a `<select>`, or the text of a `<textarea>`.
+    pub value: Option<&'a str>,
+
+    /// The field element.
+    pub element: ElementRef<'a>,
+}
+
+impl Html {
+    /// Returns every form field of the document, in document order, with the form it belongs
+    /// to.
+    ///
+    /// A field belongs to the form referenced by its `form` attribute if it has one, and to its
+    /// nearest ancestor `<form>` otherwise.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <form id="login"><input name="user"></form>
+    ///     <input name="remember" type="checkbox" form="login">
+    ///     <textarea name="notes">Hi</textarea>
+    /// "#);
+    /// let fields = document.all_inputs();
+    ///
+    /// assert_eq!(fields[1].form, fields[0].form);
+    /// assert_eq!(fields[1].field_type, "checkbox");
+    /// assert_eq!(fields[2].form, None);
+    /// assert_eq!(fields[2].value, Some("Hi"));
+    /// ```
+    pub fn all_inputs(&self) -> Vec<InputField<'_>> {
+        let mut forms = HashMap::new();
+        for element in self.tree.root().descendants().filter_map(ElementRef::wrap) {
+            if let Some(id) = element.value().id() {
+                forms.entry(id).or_insert(element);
+            }
+        }
+
+        self.select(&FIELD)
+            .map(|element| {
+                let form = match element.attr("form") {
+                    Some(id) => forms
+                        .get(id)
+                        .copied()
+                        .filter(|form| form.value().name() == "form"),
+                    None => element.closest_form(),
+                };
+                let (field_type, value) = match element.value().name() {
+                    "select" if element.attr("multiple").is_some() => {
+                        ("select-multiple", selected_value(element))
+                    }
+                    "select" => ("select", selected_value(element)),
+                    "textarea" => ("textarea", Some(element.text().next().unwrap_or_default())),
+                    _ => (input_type(element), element.attr("value")),
+                };
+                InputField {
+                    form,
+                    name: element.attr("name"),
+                    field_type,
+                    value,
+                    element,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the lowercased `type` of an `<input>`, which is `text` if missing or invalid.
+fn input_type(input: ElementRef) -> &'static str {
+    const TYPES: &[&str] = &[
+        "button",
+        "checkbox",
+        "color",
+        "date",
+        "datetime-local",
+        "email",
+        "file",
+        "hidden",
+        "image",
+        "month",
+        "number",
+        "password",
+        "radio",
+        "range",
+        "reset",
+        "search",
+        "submit",
+        "tel",
+        "text",
+        "time",
+        "url",
+        "week",
+    ];
+    input
+        .attr("type")
+        .and_then(|ty| TYPES.iter().find(|known| known.eq_ignore_ascii_case(ty)))
+        .copied()
+        .unwrap_or("text")
+}
+
+fn selected_value<'a>(select: ElementRef<'a>) -> Option<&'a str> {
+    let option = select
+        .select(&OPTION)
+        .find(|option| option.attr("selected").is_some())
+        .or_else(|| select.select(&OPTION).next())?;
+    Some(
+        option
+            .attr("value")
+            .unwrap_or_else(|| option.text().next().unwrap_or_default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn fields_with_form_association() {
+        let document = Html::parse_document(
+            r#"<form id="search">
+                <input name="q" value="rust">
+                <select name="sort"><option value="new">New</option><option selected>Top</option></select>
+                <input name="other" form="settings">
+            </form>
+            <form id="settings"><textarea name="bio"></textarea></form>
+            <div id="notform"></div>
+            <input name="page" type="hidden" value="2" form="search">
+            <input name="orphan" form="notform">
+            <select name="tags" multiple><option value="a">A</option></select>
+            <input type="submit">
+            <input name="agree" type="CHECKBOX">
+            <input name="when" type="bogus">"#,
+        );
+        let fields: Vec<_> = document
+            .all_inputs()
+            .into_iter()
+            .map(|field| {
+                (
+                    field.form.and_then(|form| form.value().id()),
+                    field.name,
+                    field.field_type,
+                    field.value,
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                (Some("search"), Some("q"), "text", Some("rust")),
+                (Some("search"), Some("sort"), "select", Some("Top")),
+                (Some("settings"), Some("other"), "text", None),
+                (Some("settings"), Some("bio"), "textarea", Some("")),
+                (Some("search"), Some("page"), "hidden", Some("2")),
+                (None, Some("orphan"), "text", None),
+                (None, Some("tags"), "select-multiple", Some("a")),
+                (None, None, "submit", None),
+                (None, Some("agree"), "checkbox", None),
+                (None, Some("when"), "text", None),
+            ]
+        );
+    }
+}
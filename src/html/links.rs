@@ -0,0 +1,132 @@
+use once_cell::sync::Lazy;
+use url::Url;
+
+use super::Html;
+use crate::Selector;
+
+static LINK: Lazy<Selector> = Lazy::new(|| Selector::parse("a[href], area[href]").unwrap());
+
+impl Html {
+    /// Returns true if two hrefs resolve against a base URL to the same resource.
+    ///
+    /// Resolution normalizes the scheme and host case, default ports, dot segments and
+    /// percent-encoding. Fragments are ignored, since they refer to parts of the same resource.
+    /// Returns false if either href cannot be resolved.
+    ///
+    /// ```
+    /// use scraper::Html;
+    /// use url::Url;
+    ///
+    /// let base = Url::parse("https://example.com/docs/").unwrap();
+    ///
+    /// assert!(Html::same_resource("./intro", "/docs/intro#usage", &base));
+    /// assert!(!Html::same_resource("intro", "../intro", &base));
+    /// ```
+    pub fn same_resource(a: &str, b: &str, base: &Url) -> bool {
+        let resolve = |href: &str| {
+            base.join(href.trim()).ok().map(|mut url| {
+                url.set_fragment(None);
+                url
+            })
+        };
+        match (resolve(a), resolve(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Resolves the hrefs of `<a>` and `<area>` elements against a base URL and splits them into
+    /// internal and external links, in document order.
+    ///
+    /// A link is internal if its host equals the host of the base URL, regardless of scheme or
+    /// port. Only `http` and `https` links are returned, so `mailto:`, `tel:` and `javascript:`
+    /// links, as well as hrefs which cannot be resolved, are excluded.
+    ///
+    /// ```
+    /// use scraper::Html;
+    /// use url::Url;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <a href="/about">About</a>
+    ///     <a href="https://other.org/">Other</a>
+    /// "#);
+    /// let base = Url::parse("https://example.com/").unwrap();
+    ///
+    /// let (internal, external) = document.classify_links(&base);
+    /// assert_eq!(internal, ["https://example.com/about"]);
+    /// assert_eq!(external, ["https://other.org/"]);
+    /// ```
+    pub fn classify_links(&self, base: &Url) -> (Vec<String>, Vec<String>) {
+        let mut internal = Vec::new();
+        let mut external = Vec::new();
+        for link in self.select(&LINK) {
+            let url = match link
+                .attr("href")
+                .and_then(|href| base.join(href.trim()).ok())
+            {
+                Some(url) if matches!(url.scheme(), "http" | "https") => url,
+                _ => continue,
+            };
+            if url.host_str() == base.host_str() {
+                internal.push(url.into());
+            } else {
+                external.push(url.into());
+            }
+        }
+        (internal, external)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::Html;
+
+    #[test]
+    fn same_resource_after_resolution() {
+        let base = Url::parse("https://example.com/dir/page.html").unwrap();
+
+        assert!(Html::same_resource("./a", "/dir/a", &base));
+        assert!(Html::same_resource("a#x", "a#y", &base));
+        assert!(Html::same_resource("", "#top", &base));
+        assert!(Html::same_resource(
+            "HTTPS://Example.COM:443/dir/./b/../a",
+            "a",
+            &base
+        ));
+        assert!(Html::same_resource(" a ", "a", &base));
+
+        assert!(!Html::same_resource("a", "a?q=1", &base));
+        assert!(!Html::same_resource("a", "http://example.com/dir/a", &base));
+        assert!(!Html::same_resource("a", "http://[::1", &base));
+    }
+
+    #[test]
+    fn classify_links_by_host() {
+        let document = Html::parse_document(
+            r#"<a href="next.html">Next</a>
+            <a href="http://EXAMPLE.com:8080/admin">Admin</a>
+            <a href="https://cdn.example.com/file.pdf">File</a>
+            <map><area href="//other.org/map"></map>
+            <a href="mailto:team@example.com">Mail</a>
+            <a href="javascript:void(0)">Menu</a>
+            <a href="http://[::1">Broken</a>
+            <a>No href</a>"#,
+        );
+        let base = Url::parse("https://example.com/dir/page.html").unwrap();
+
+        let (internal, external) = document.classify_links(&base);
+        assert_eq!(
+            internal,
+            [
+                "https://example.com/dir/next.html",
+                "http://example.com:8080/admin"
+            ]
+        );
+        assert_eq!(
+            external,
+            ["https://cdn.example.com/file.pdf", "https://other.org/map"]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static VIEWPORT: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("meta[name=viewport i][content]").unwrap());
+static ROBOTS: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("meta[name=robots i][content]").unwrap());
+static REFRESH: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("meta[http-equiv=refresh i][content]").unwrap());
+static HREFLANG: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("link[rel~=alternate i][hreflang][href]").unwrap());
+static OG_IMAGE: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse(
+        r#"meta[property="og:image" i][content], meta[property="og:image:url" i][content]"#,
+    )
+    .unwrap()
+});
+static IMAGE_SRC: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("link[rel~=image_src i][href]").unwrap());
+static IMG: Lazy<Selector> = Lazy::new(|| Selector::parse("img[src]").unwrap());
+static AMPHTML: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("link[rel~=amphtml i][href]").unwrap());
+static GENERATOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("meta[name=generator i][content]").unwrap());
+
+/// Markup left behind by common site generators and frameworks, checked in order when there is
+/// no `<meta name="generator">`. Add an entry to recognize another platform.
+static FINGERPRINTS: Lazy<Vec<(&str, Selector)>> = Lazy::new(|| {
+    [
+        ("Next.js", "script#__NEXT_DATA__, script[src*='/_next/']"),
+        ("Nuxt", "#__nuxt, script#__NUXT_DATA__"),
+        ("Gatsby", "#___gatsby"),
+        (
+            "WordPress",
+            "link[href*='/wp-content/'], script[src*='/wp-content/'], \
+             link[href*='/wp-includes/'], script[src*='/wp-includes/']",
+        ),
+        (
+            "Drupal",
+            "[data-drupal-selector], script[src*='/core/misc/drupal']",
+        ),
+        (
+            "Shopify",
+            "link[href*='cdn.shopify.com'], script[src*='cdn.shopify.com']",
+        ),
+        ("Angular", "[ng-version]"),
+    ]
+    .into_iter()
+    .map(|(name, selector)| (name, Selector::parse(selector).unwrap()))
+    .collect()
+});
+
+impl Html {
+    /// Returns the content of `<meta name="viewport">`.
+    pub fn viewport(&self) -> Option<&str> {
+        self.select(&VIEWPORT)
+            .next()
+            .and_then(|meta| meta.attr("content"))
+    }
+
+    /// Returns the comma-separated directives of all `<meta name="robots">` elements.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"<meta name="robots" content="noindex, nofollow">"#);
+    /// assert_eq!(document.robots(), ["noindex", "nofollow"]);
+    /// ```
+    pub fn robots(&self) -> Vec<&str> {
+        self.select(&ROBOTS)
+            .filter_map(|meta| meta.attr("content"))
+            .flat_map(|content| content.split(','))
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .collect()
+    }
+
+    /// Returns the delay in seconds and the target URL of `<meta http-equiv="refresh">`.
+    ///
+    /// The target is `None` if the page only reloads itself. Returns `None` if the content is
+    /// malformed.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document =
+    ///     Html::parse_document(r#"<meta http-equiv="refresh" content="0; url=/next">"#);
+    /// assert_eq!(document.meta_refresh(), Some((0, Some("/next".to_owned()))));
+    /// ```
+    pub fn meta_refresh(&self) -> Option<(u32, Option<String>)> {
+        self.select(&REFRESH)
+            .next()
+            .and_then(|meta| meta.attr("content"))
+            .and_then(parse_refresh)
+    }
+
+    /// Returns the `(hreflang, href)` pairs of `<link rel="alternate" hreflang>` elements.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <link rel="alternate" hreflang="de" href="https://example.com/de/">
+    /// "#);
+    /// assert_eq!(
+    ///     document.alternate_languages(),
+    ///     [("de".to_owned(), "https://example.com/de/".to_owned())]
+    /// );
+    /// ```
+    pub fn alternate_languages(&self) -> Vec<(String, String)> {
+        self.select(&HREFLANG)
+            .filter_map(|link| {
+                let lang = link.attr("hreflang")?.trim();
+                let href = link.attr("href")?.trim();
+                Some((lang.to_owned(), href.to_owned()))
+            })
+            .collect()
+    }
+
+    /// Returns the URL of the image best representing the page.
+    ///
+    /// In order of preference, this is the `og:image`, the `<link rel="image_src">` or the
+    /// `<img>` with the largest declared `width` and `height`. If no image declares both, the
+    /// first one is used. Images declared one pixel or less wide or high, such as tracking
+    /// pixels, are ignored.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <img src="/logo.png" width="40" height="40"><img src="/hero.jpg" width="800" height="400">
+    /// "#);
+    /// assert_eq!(document.primary_image().as_deref(), Some("/hero.jpg"));
+    /// ```
+    pub fn primary_image(&self) -> Option<String> {
+        let declared = self
+            .select(&OG_IMAGE)
+            .filter_map(|meta| meta.attr("content"))
+            .chain(self.select(&IMAGE_SRC).filter_map(|link| link.attr("href")))
+            .map(str::trim)
+            .find(|url| !url.is_empty());
+        if let Some(url) = declared {
+            return Some(url.to_owned());
+        }
+
+        let dimension = |img: ElementRef, name| -> Option<u64> {
+            let value = img.attr(name)?.trim();
+            let digits = value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len());
+            value[..digits].parse().ok()
+        };
+        let mut first = None;
+        let mut largest: Option<(u64, &str)> = None;
+        for img in self.select(&IMG) {
+            let src = img.attr("src").unwrap_or_default().trim();
+            let (width, height) = (dimension(img, "width"), dimension(img, "height"));
+            if src.is_empty() || width.is_some_and(|w| w <= 1) || height.is_some_and(|h| h <= 1) {
+                continue;
+            }
+            first = first.or(Some(src));
+            if let (Some(width), Some(height)) = (width, height) {
+                let area = width.saturating_mul(height);
+                if largest.is_none_or(|(largest, _)| area > largest) {
+                    largest = Some((area, src));
+                }
+            }
+        }
+        largest.map(|(_, src)| src).or(first).map(str::to_owned)
+    }
+
+    /// Returns the URL of the AMP version of the page from `<link rel="amphtml">`.
+    pub fn amp_url(&self) -> Option<&str> {
+        self.select(&AMPHTML)
+            .next()
+            .and_then(|link| link.attr("href"))
+    }
+
+    /// Returns true if the page is an AMP page, i.e. its `<html>` element has an `amp` or `⚡`
+    /// attribute.
+    pub fn is_amp(&self) -> bool {
+        let html = self.root_element();
+        html.attr("amp").is_some() || html.attr("⚡").is_some()
+    }
+
+    /// Guesses the site generator or framework which produced the page.
+    ///
+    /// The content of `<meta name="generator">` is used if present, shortened to the name of a
+    /// recognized platform such as WordPress. Otherwise, the page is checked for markup
+    /// characteristic of common platforms, such as the `__NEXT_DATA__` script of Next.js or
+    /// `/wp-content/` asset paths of WordPress.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"<meta name="generator" content="WordPress 6.0">"#);
+    /// assert_eq!(document.generator().as_deref(), Some("WordPress"));
+    /// ```
+    pub fn generator(&self) -> Option<String> {
+        let meta = self
+            .select(&GENERATOR)
+            .filter_map(|meta| meta.attr("content"))
+            .map(str::trim)
+            .find(|content| !content.is_empty());
+        if let Some(content) = meta {
+            let lowercase = content.to_lowercase();
+            let known = FINGERPRINTS
+                .iter()
+                .map(|(name, _)| *name)
+                .find(|name| lowercase.starts_with(&name.to_lowercase()));
+            return Some(known.unwrap_or(content).to_owned());
+        }
+
+        FINGERPRINTS
+            .iter()
+            .find(|(_, selector)| self.exists(selector))
+            .map(|(name, _)| (*name).to_owned())
+    }
+}
+
+const WHITESPACE: &[char] = &['\t', '\n', '\x0C', '\r', ' '];
+
+/// Parses the content of a refresh declaration, roughly following the HTML specification.
+fn parse_refresh(content: &str) -> Option<(u32, Option<String>)> {
+    let content = content.trim_start_matches(WHITESPACE);
+    let digits = content
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(content.len());
+    if digits == 0 && !content.starts_with('.') {
+        return None;
+    }
+    let delay = if digits == 0 {
+        0
+    } else {
+        content[..digits].parse().unwrap_or(u32::MAX)
+    };
+
+    let rest = content[digits..]
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .trim_start_matches(WHITESPACE);
+    let rest = match rest.chars().next() {
+        None => return Some((delay, None)),
+        Some(';' | ',') => rest[1..].trim_start_matches(WHITESPACE),
+        // The URL may also be separated from the delay by whitespace only.
+        Some(_) if rest.len() < content[digits..].len() => rest,
+        Some(_) => return None,
+    };
+
+    let mut url = rest;
+    if url
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("url"))
+    {
+        let after = url[3..].trim_start_matches(WHITESPACE);
+        if let Some(after) = after.strip_prefix('=') {
+            url = after.trim_start_matches(WHITESPACE);
+        }
+    }
+    if let Some(quote) = url.chars().next().filter(|&c| c == '"' || c == '\'') {
+        url = &url[1..];
+        if let Some(end) = url.find(quote) {
+            url = &url[..end];
+        }
+    }
+
+    let url = url.trim_end_matches(WHITESPACE);
+    Some((delay, Some(url.to_owned()).filter(|url| !url.is_empty())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn viewport_and_robots() {
+        let html = Html::parse_document(
+            r#"<head>
+            <meta name="Viewport" content="width=device-width, initial-scale=1">
+            <meta name="robots" content="noindex, nofollow">
+            <meta name="ROBOTS" content=" noarchive ,">
+            <meta name="googlebot" content="nosnippet">
+            </head>"#,
+        );
+        assert_eq!(html.viewport(), Some("width=device-width, initial-scale=1"));
+        assert_eq!(html.robots(), ["noindex", "nofollow", "noarchive"]);
+
+        let html = Html::parse_document("<p>no meta</p>");
+        assert_eq!(html.viewport(), None);
+        assert!(html.robots().is_empty());
+    }
+
+    #[test]
+    fn meta_refresh_content() {
+        let refresh = |content: &str| {
+            Html::parse_document(&format!(
+                r#"<meta http-equiv="Refresh" content="{}">"#,
+                content
+            ))
+            .meta_refresh()
+        };
+        let to = |delay, url: &str| Some((delay, Some(url.to_owned())));
+
+        assert_eq!(refresh("0; url=/next"), to(0, "/next"));
+        assert_eq!(
+            refresh("5;URL = 'https://example.com/?a=1'"),
+            to(5, "https://example.com/?a=1")
+        );
+        assert_eq!(refresh(" 3, /plain "), to(3, "/plain"));
+        assert_eq!(refresh("1.5 url=/x"), to(1, "/x"));
+        assert_eq!(refresh("0; ééx"), to(0, "ééx"));
+        assert_eq!(refresh("0; url=/café"), to(0, "/café"));
+        assert_eq!(refresh("10"), Some((10, None)));
+        assert_eq!(refresh("10;"), Some((10, None)));
+        assert_eq!(refresh("soon"), None);
+        assert_eq!(refresh("5x"), None);
+        assert_eq!(Html::parse_document("<p></p>").meta_refresh(), None);
+    }
+
+    #[test]
+    fn hreflang_alternates() {
+        let html = Html::parse_document(
+            r#"<head>
+            <link rel="alternate" hreflang="en-GB" href="https://example.com/uk/">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+            <link rel="Alternate" hreflang="x-default" href=" https://example.com/ ">
+            <link rel="canonical" hreflang="fr" href="https://example.com/fr/">
+            </head>"#,
+        );
+        assert_eq!(
+            html.alternate_languages(),
+            [
+                ("en-GB".to_owned(), "https://example.com/uk/".to_owned()),
+                ("x-default".to_owned(), "https://example.com/".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn primary_image_preference() {
+        let html = Html::parse_document(
+            r#"<head>
+            <link rel="image_src" href="/link.png">
+            <meta property="og:image" content="https://example.com/og.png">
+            </head><body><img src="/big.png" width="1000" height="1000"></body>"#,
+        );
+        assert_eq!(
+            html.primary_image().as_deref(),
+            Some("https://example.com/og.png")
+        );
+
+        let html = Html::parse_document(
+            r#"<link rel="image_src" href="/link.png"><img src="/big.png" width="1000" height="1000">"#,
+        );
+        assert_eq!(html.primary_image().as_deref(), Some("/link.png"));
+
+        let html = Html::parse_document(
+            r#"<img src="/pixel.gif" width="1" height="1">
+            <img src="/small.png" width="100px" height="50">
+            <img src="/unsized.png">
+            <img src="/large.png" width="300" height="200">"#,
+        );
+        assert_eq!(html.primary_image().as_deref(), Some("/large.png"));
+
+        let html = Html::parse_document(
+            r#"<img src="/pixel.gif" width="1" height="1"><img src=""><img src="/first.png"><img src="/second.png">"#,
+        );
+        assert_eq!(html.primary_image().as_deref(), Some("/first.png"));
+
+        assert_eq!(Html::parse_document("<p></p>").primary_image(), None);
+    }
+
+    #[test]
+    fn generator_from_meta_and_fingerprints() {
+        let generator = |html: &str| Html::parse_document(html).generator();
+
+        assert_eq!(
+            generator(r#"<meta name="generator" content="WordPress 6.0">"#).as_deref(),
+            Some("WordPress")
+        );
+        assert_eq!(
+            generator(r#"<meta name="Generator" content=" Hugo 0.120.0 ">"#).as_deref(),
+            Some("Hugo 0.120.0")
+        );
+        assert_eq!(
+            generator(
+                r#"<div id="__next"></div>
+                <script id="__NEXT_DATA__" type="application/json">{"props":{}}</script>"#
+            )
+            .as_deref(),
+            Some("Next.js")
+        );
+        assert_eq!(
+            generator(
+                r#"<link rel="stylesheet" href="https://example.com/wp-content/themes/a/style.css">"#
+            )
+            .as_deref(),
+            Some("WordPress")
+        );
+        assert_eq!(
+            generator(r#"<app-root ng-version="17.0.0"></app-root>"#).as_deref(),
+            Some("Angular")
+        );
+        assert_eq!(generator("<p>Plain</p>"), None);
+    }
+
+    #[test]
+    fn amp_page() {
+        let html = Html::parse_document(r#"<!DOCTYPE html><html amp><head></head></html>"#);
+        assert!(html.is_amp());
+
+        let html = Html::parse_document(r#"<!DOCTYPE html><html ⚡ lang="en"></html>"#);
+        assert!(html.is_amp());
+        assert_eq!(html.amp_url(), None);
+    }
+
+    #[test]
+    fn canonical_page_with_amp_link() {
+        let html = Html::parse_document(
+            r#"<!DOCTYPE html><html><head>
+            <link rel="canonical" href="https://example.com/a">
+            <link rel="AmpHtml" href="https://example.com/a.amp">
+            </head></html>"#,
+        );
+        assert!(!html.is_amp());
+        assert_eq!(html.amp_url(), Some("https://example.com/a.amp"));
+    }
+}
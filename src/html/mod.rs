@@ -2,16 +2,20 @@
 
 #[cfg(feature = "errors")]
 use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io;
 use std::iter::FusedIterator;
 
-use ego_tree::iter::Nodes;
-use ego_tree::Tree;
-use html5ever::serialize::SerializeOpts;
+use ego_tree::iter::{Edge, Nodes, Traverse};
+use ego_tree::{NodeId, NodeRef, Tree};
+use html5ever::serialize::{HtmlSerializer, SerializeOpts, Serializer, TraversalScope};
 use html5ever::tree_builder::QuirksMode;
 use html5ever::QualName;
 use html5ever::{driver, serialize};
 use tendril::TendrilSink;
 
+use crate::element_ref::append_copy;
 use crate::selector::Selector;
 use crate::{ElementRef, Node};
 
@@ -34,6 +38,30 @@ pub struct Html {
     pub tree: Tree<Node>,
 }
 
+/// Options for parsing HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlParseOptions {
+    /// Parse as if scripting were enabled, so that the content of `<noscript>` is a single text
+    /// node rather than markup. Enabled by default.
+    pub scripting_enabled: bool,
+}
+
+impl Default for HtmlParseOptions {
+    fn default() -> Self {
+        HtmlParseOptions {
+            scripting_enabled: true,
+        }
+    }
+}
+
+impl HtmlParseOptions {
+    fn parse_opts(&self) -> driver::ParseOpts {
+        let mut opts = driver::ParseOpts::default();
+        opts.tree_builder.scripting_enabled = self.scripting_enabled;
+        opts
+    }
+}
+
 impl Html {
     /// Creates an empty HTML document.
     pub fn new_document() -> Self {
@@ -74,22 +102,68 @@ impl Html {
     /// # }
     /// ```
     pub fn parse_document(document: &str) -> Self {
-        let parser = driver::parse_document(Self::new_document(), Default::default());
+        Self::parse_document_with_options(document, HtmlParseOptions::default())
+    }
+
+    /// Parses a string of HTML as a document with the given options.
+    ///
+    /// ```
+    /// use scraper::html::HtmlParseOptions;
+    /// use scraper::{Html, Selector};
+    ///
+    /// let options = HtmlParseOptions { scripting_enabled: false };
+    /// let document = Html::parse_document_with_options(
+    ///     "<body><noscript><img></noscript></body>",
+    ///     options,
+    /// );
+    ///
+    /// assert!(document.exists(&Selector::parse("noscript > img").unwrap()));
+    /// ```
+    pub fn parse_document_with_options(document: &str, options: HtmlParseOptions) -> Self {
+        let parser = driver::parse_document(Self::new_document(), options.parse_opts());
         parser.one(document)
     }
 
     /// Parses a string of HTML as a fragment.
     pub fn parse_fragment(fragment: &str) -> Self {
+        Self::parse_fragment_with_options(fragment, HtmlParseOptions::default())
+    }
+
+    /// Parses a string of HTML as a fragment with the given options.
+    pub fn parse_fragment_with_options(fragment: &str, options: HtmlParseOptions) -> Self {
         let parser = driver::parse_fragment(
             Self::new_fragment(),
-            Default::default(),
+            options.parse_opts(),
             QualName::new(None, ns!(html), local_name!("body")),
             Vec::new(),
         );
         parser.one(fragment)
     }
 
+    /// Parses a string of HTML as a document or a fragment.
+    ///
+    /// The input is parsed as a document if it contains a doctype or an `<html>`, `<head>` or
+    /// `<body>` tag, matched case-insensitively. Any other input, including ambiguous input, is
+    /// parsed as a fragment.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// assert!(Html::parse("<!DOCTYPE html><title>Page</title>").tree.root().value().is_document());
+    /// assert!(Html::parse("<span>Hi</span>").tree.root().value().is_fragment());
+    /// ```
+    pub fn parse(html: &str) -> Self {
+        if looks_like_document(html) {
+            Self::parse_document(html)
+        } else {
+            Self::parse_fragment(html)
+        }
+    }
+
     /// Returns an iterator over elements matching a selector.
+    ///
+    /// Dynamic pseudo-classes such as `:hover` never match; see
+    /// [`Selector::has_dynamic_pseudo`].
     pub fn select<'a, 'b>(&'a self, selector: &'b Selector) -> Select<'a, 'b> {
         Select {
             inner: self.tree.nodes(),
@@ -97,8 +171,380 @@ impl Html {
         }
     }
 
+    /// Maps the elements matching a selector and collects the results.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let fragment = Html::parse_fragment(r#"<a href="/a">A</a><a href="/b">B</a>"#);
+    /// let a = Selector::parse("a").unwrap();
+    ///
+    /// let hrefs = fragment.select_map(&a, |a| a.attr("href"));
+    /// assert_eq!(hrefs, [Some("/a"), Some("/b")]);
+    /// ```
+    pub fn select_map<'a, T, F>(&'a self, selector: &Selector, f: F) -> Vec<T>
+    where
+        F: Fn(ElementRef<'a>) -> T,
+    {
+        self.select(selector).map(f).collect()
+    }
+
+    /// Returns an iterator over elements matching `include` which are not inside an element
+    /// matching `exclude`.
+    ///
+    /// Elements matching `exclude` are themselves excluded.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let html = Html::parse_document(r#"
+    ///     <main><a href="/article">Article</a></main>
+    ///     <div class="footer"><a href="/about">About</a></div>
+    /// "#);
+    /// let links = Selector::parse("a").unwrap();
+    /// let footer = Selector::parse(".footer").unwrap();
+    ///
+    /// let hrefs: Vec<_> = html
+    ///     .select_excluding(&links, &footer)
+    ///     .map(|a| a.attr("href").unwrap())
+    ///     .collect();
+    /// assert_eq!(hrefs, ["/article"]);
+    /// ```
+    pub fn select_excluding<'a, 'b>(
+        &'a self,
+        include: &'b Selector,
+        exclude: &'b Selector,
+    ) -> SelectExcluding<'a, 'b> {
+        SelectExcluding {
+            inner: self.tree.root().traverse(),
+            include,
+            exclude,
+            excluded: None,
+        }
+    }
+
+    /// Returns an iterator over the elements matching any of the selectors, in document order.
+    ///
+    /// The document is traversed once, so an element matching several selectors is yielded
+    /// once.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document(r#"<a class="btn">1</a><button class="btn">2</button>"#);
+    /// let a = Selector::parse("a").unwrap();
+    /// let btn = Selector::parse(".btn").unwrap();
+    ///
+    /// assert_eq!(document.select_union(&[&a, &btn]).count(), 2);
+    /// ```
+    pub fn select_union<'a, 'b>(
+        &'a self,
+        selectors: &'b [&'b Selector],
+    ) -> impl Iterator<Item = ElementRef<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        self.tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(move |element| selectors.iter().any(|selector| selector.matches(element)))
+    }
+
+    /// Replaces the contents of `buf` with the elements matching the selector.
+    ///
+    /// Reusing the buffer across calls avoids allocating a new vector for each selection.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document("<p>1</p><p>2</p><a>3</a>");
+    /// let mut buf = Vec::new();
+    ///
+    /// document.select_into(&Selector::parse("p").unwrap(), &mut buf);
+    /// assert_eq!(buf.len(), 2);
+    /// document.select_into(&Selector::parse("a").unwrap(), &mut buf);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    pub fn select_into<'a>(&'a self, selector: &Selector, buf: &mut Vec<ElementRef<'a>>) {
+        buf.clear();
+        buf.extend(self.select(selector));
+    }
+
+    /// Returns the first non-empty trimmed text of an element matching one of the selectors.
+    ///
+    /// Selectors are tried in order, so later ones act as fallbacks.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document(r#"<div class="title"> Hello </div>"#);
+    /// let h1 = Selector::parse("h1").unwrap();
+    /// let title = Selector::parse(".title").unwrap();
+    ///
+    /// assert_eq!(document.first_text(&[&h1, &title]).as_deref(), Some("Hello"));
+    /// ```
+    pub fn first_text(&self, selectors: &[&Selector]) -> Option<String> {
+        selectors.iter().find_map(|selector| {
+            self.select(selector).find_map(|element| {
+                let text: String = element.text().collect();
+                let text = text.trim();
+                (!text.is_empty()).then(|| text.to_owned())
+            })
+        })
+    }
+
+    /// Writes the HTML of each element matching the selector to a writer, as they are matched.
+    ///
+    /// Unlike collecting the [`ElementRef::html`] of each match, memory use does not grow with
+    /// the number of matches.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document("<ul><li>1</li><li>2</li></ul>");
+    /// let mut out = Vec::new();
+    /// document.write_select(&Selector::parse("li").unwrap(), &mut out).unwrap();
+    ///
+    /// assert_eq!(out, b"<li>1</li><li>2</li>");
+    /// ```
+    pub fn write_select<W: io::Write>(
+        &self,
+        selector: &Selector,
+        writer: &mut W,
+    ) -> io::Result<()> {
+        for element in self.select(selector) {
+            element.write_html(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the elements matching the selector whose combined text equals `text`.
+    ///
+    /// If `trim` is true, leading and trailing whitespace of the element's text is ignored.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document("<button>Submit Order</button><button> Submit </button>");
+    /// let button = Selector::parse("button").unwrap();
+    ///
+    /// let submit = document.select_text_eq(&button, "Submit", true).next().unwrap();
+    /// assert_eq!(submit.inner_html(), " Submit ");
+    /// ```
+    pub fn select_text_eq<'a, 'b>(
+        &'a self,
+        selector: &'b Selector,
+        text: &'b str,
+        trim: bool,
+    ) -> impl Iterator<Item = ElementRef<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        self.select(selector).filter(move |element| {
+            let combined: String = element.text().collect();
+            if trim {
+                combined.trim() == text
+            } else {
+                combined == text
+            }
+        })
+    }
+
+    /// Returns an iterator over the elements having every one of the classes.
+    ///
+    /// This is equivalent to a `.a.b` selector without building and escaping its string.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<p class="note warn">1</p><p class="note">2</p>"#);
+    /// let warnings: Vec<_> = fragment.select_classes(&["note", "warn"]).collect();
+    ///
+    /// assert_eq!(warnings.len(), 1);
+    /// assert_eq!(warnings[0].inner_html(), "1");
+    /// ```
+    pub fn select_classes<'a, 'b>(
+        &'a self,
+        classes: &'b [&'b str],
+    ) -> impl Iterator<Item = ElementRef<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        self.tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(move |element| element.has_all_classes(classes))
+    }
+
+    /// Returns an iterator over elements with a `data-<key>` attribute, equal to `value` if
+    /// given.
+    ///
+    /// Attribute names are lowercased by the parser, so the key is matched case-insensitively.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let fragment = Html::parse_fragment(r#"
+    ///     <button data-testid="cancel">Cancel</button>
+    ///     <button data-testid="submit">Send</button>
+    /// "#);
+    ///
+    /// let submit = fragment.select_data("testid", Some("submit")).next().unwrap();
+    /// assert_eq!(submit.inner_html(), "Send");
+    /// assert_eq!(fragment.select_data("testid", None).count(), 2);
+    /// ```
+    pub fn select_data<'a, 'b>(
+        &'a self,
+        key: &str,
+        value: Option<&'b str>,
+    ) -> impl Iterator<Item = ElementRef<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        let name = format!("data-{}", key.to_ascii_lowercase());
+        self.tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(move |element| {
+                element
+                    .attr(&name)
+                    .is_some_and(|actual| value.is_none_or(|value| actual == value))
+            })
+    }
+
+    /// Returns true if any element matches the selector.
+    ///
+    /// Stops at the first match instead of traversing the whole document.
+    pub fn exists(&self, selector: &Selector) -> bool {
+        self.select(selector).next().is_some()
+    }
+
+    /// Counts the elements matching each of the named selectors in a single traversal.
+    ///
+    /// Every name is present in the result, with a count of zero if nothing matched.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document("<a></a><a></a><img>");
+    /// let a = Selector::parse("a").unwrap();
+    /// let img = Selector::parse("img").unwrap();
+    ///
+    /// let counts = document.count_many(&[("links", &a), ("images", &img)]);
+    /// assert_eq!(counts["links"], 2);
+    /// assert_eq!(counts["images"], 1);
+    /// ```
+    pub fn count_many(&self, selectors: &[(&str, &Selector)]) -> HashMap<String, usize> {
+        let mut counts = vec![0; selectors.len()];
+        for element in self.tree.root().descendants().filter_map(ElementRef::wrap) {
+            for (count, (_, selector)) in counts.iter_mut().zip(selectors) {
+                if selector.matches(&element) {
+                    *count += 1;
+                }
+            }
+        }
+
+        selectors
+            .iter()
+            .zip(counts)
+            .map(|((name, _), count)| ((*name).to_owned(), count))
+            .collect()
+    }
+
+    /// Returns the set of distinct element tag names used in the document.
+    pub fn tag_names(&self) -> BTreeSet<String> {
+        self.tree
+            .values()
+            .filter_map(Node::as_element)
+            .map(|element| element.name().to_owned())
+            .collect()
+    }
+
+    /// Returns the elements having the given attribute, indexed by its value.
+    ///
+    /// Elements are listed in document order for each value.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<li data-id="7">Seven</li><li data-id="8">Eight</li>"#);
+    /// let index = fragment.index_by_attr("data-id");
+    ///
+    /// assert_eq!(index["8"][0].inner_html(), "Eight");
+    /// ```
+    pub fn index_by_attr(&self, attr: &str) -> HashMap<&str, Vec<ElementRef<'_>>> {
+        let mut index: HashMap<_, Vec<_>> = HashMap::new();
+        for element in self.tree.root().descendants().filter_map(ElementRef::wrap) {
+            if let Some(value) = element.attr(attr) {
+                index.entry(value).or_default().push(element);
+            }
+        }
+        index
+    }
+
+    /// Returns the ids used by more than one element, with those elements.
+    ///
+    /// Ids are listed in order of first occurrence and elements in document order.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<p id="x">1</p><p id="y">2</p><p id="x">3</p>"#);
+    /// let duplicates = fragment.duplicate_ids();
+    ///
+    /// assert_eq!(duplicates.len(), 1);
+    /// assert_eq!(duplicates[0].0, "x");
+    /// assert_eq!(duplicates[0].1.len(), 2);
+    /// ```
+    pub fn duplicate_ids(&self) -> Vec<(String, Vec<ElementRef<'_>>)> {
+        let mut ids: Vec<(&str, Vec<_>)> = Vec::new();
+        let mut positions = HashMap::new();
+        for element in self.tree.root().descendants().filter_map(ElementRef::wrap) {
+            if let Some(id) = element.value().id() {
+                let position = *positions.entry(id).or_insert_with(|| {
+                    ids.push((id, Vec::new()));
+                    ids.len() - 1
+                });
+                ids[position].1.push(element);
+            }
+        }
+        ids.into_iter()
+            .filter(|(_, elements)| elements.len() > 1)
+            .map(|(id, elements)| (id.to_owned(), elements))
+            .collect()
+    }
+
+    /// Returns the node at a path of child indices from the document, such as one returned by
+    /// [`ElementRef::index_path`].
+    pub fn at_index_path(&self, path: &[usize]) -> Option<NodeRef<'_, Node>> {
+        path.iter()
+            .try_fold(self.tree.root(), |node, &index| node.children().nth(index))
+    }
+
+    /// Copies an element and its descendants into a new fragment whose root element is the copy.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document(r#"<p>Intro</p><div class="article"><p>Body</p></div>"#);
+    /// let article = document.select(&Selector::parse(".article").unwrap()).next().unwrap();
+    ///
+    /// let article = document.reroot(article);
+    /// assert_eq!(article.root_element().value().name(), "div");
+    /// assert_eq!(article.select(&Selector::parse("p").unwrap()).count(), 1);
+    /// ```
+    pub fn reroot(&self, element: ElementRef) -> Html {
+        let mut html = Html::new_fragment();
+        html.quirks_mode = self.quirks_mode;
+        append_copy(*element, &mut html.tree.root_mut());
+        html
+    }
+
     /// Returns the root `<html>` element.
-    pub fn root_element(&self) -> ElementRef {
+    pub fn root_element(&self) -> ElementRef<'_> {
         let root_node = self
             .tree
             .root()
@@ -112,13 +558,69 @@ impl Html {
     pub fn html(&self) -> String {
         let opts = SerializeOpts {
             scripting_enabled: false, // It's not clear what this does.
-            traversal_scope: html5ever::serialize::TraversalScope::IncludeNode,
+            traversal_scope: TraversalScope::IncludeNode,
             create_missing_parent: false,
         };
         let mut buf = Vec::new();
         serialize(&mut buf, self, opts).unwrap();
         String::from_utf8(buf).unwrap()
     }
+
+    /// Serialize entire document into HTML, replacing any existing doctype with `<!DOCTYPE name>`.
+    ///
+    /// The doctype is inserted at the start of the output even if the document did not declare
+    /// one, e.g. when normalizing quirks-mode documents.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document("<title>Legacy</title>");
+    /// assert_eq!(
+    ///     document.with_doctype("html"),
+    ///     "<!DOCTYPE html><html><head><title>Legacy</title></head><body></body></html>"
+    /// );
+    /// ```
+    pub fn with_doctype(&self, name: &str) -> String {
+        let opts = SerializeOpts {
+            scripting_enabled: false, // It's not clear what this does.
+            traversal_scope: TraversalScope::IncludeNode,
+            create_missing_parent: false,
+        };
+        let mut buf = Vec::new();
+        let mut serializer = HtmlSerializer::new(&mut buf, opts);
+        serializer.write_doctype(name).unwrap();
+        for child in self.tree.root().children() {
+            if !child.value().is_doctype() {
+                crate::node::serializable::serialize(
+                    child,
+                    &mut serializer,
+                    TraversalScope::IncludeNode,
+                )
+                .unwrap();
+            }
+        }
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+/// Formats the document as its HTML.
+impl fmt::Display for Html {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.html())
+    }
+}
+
+fn looks_like_document(html: &str) -> bool {
+    html.match_indices('<').any(|(i, _)| {
+        let tag = &html.as_bytes()[i + 1..];
+        ["!doctype", "html", "head", "body"].iter().any(|name| {
+            tag.len() >= name.len()
+                && tag[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+                && tag
+                    .get(name.len())
+                    .is_none_or(|&c| c.is_ascii_whitespace() || c == b'>' || c == b'/')
+        })
+    })
 }
 
 /// Iterator over elements matching a selector.
@@ -164,13 +666,101 @@ impl<'a, 'b> DoubleEndedIterator for Select<'a, 'b> {
 
 impl FusedIterator for Select<'_, '_> {}
 
+/// Iterator over elements matching a selector outside of excluded subtrees.
+#[derive(Debug)]
+pub struct SelectExcluding<'a, 'b> {
+    inner: Traverse<'a, Node>,
+    include: &'b Selector,
+    exclude: &'b Selector,
+    excluded: Option<NodeId>,
+}
+
+impl<'a, 'b> Iterator for SelectExcluding<'a, 'b> {
+    type Item = ElementRef<'a>;
+
+    fn next(&mut self) -> Option<ElementRef<'a>> {
+        for edge in &mut self.inner {
+            match edge {
+                Edge::Open(node) if self.excluded.is_none() => {
+                    if let Some(element) = ElementRef::wrap(node) {
+                        if self.exclude.matches(&element) {
+                            self.excluded = Some(node.id());
+                        } else if self.include.matches(&element) {
+                            return Some(element);
+                        }
+                    }
+                }
+                Edge::Close(node) if self.excluded == Some(node.id()) => {
+                    self.excluded = None;
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for SelectExcluding<'_, '_> {}
+
+mod breadcrumbs;
+mod forms;
+#[cfg(feature = "url")]
+mod links;
+mod meta;
+mod outline;
+mod script;
 mod serializable;
+mod set;
+mod structure;
+mod text;
+mod time;
+mod transform;
+#[cfg(feature = "serde_json")]
+mod tree_json;
 mod tree_sink;
 
+pub use self::forms::InputField;
+pub use self::script::ScriptInfo;
+pub use self::set::HtmlSet;
+pub use self::structure::{StructureRule, Violation};
+
+#[cfg(feature = "chrono")]
+pub use self::time::TimeValue;
+
 #[cfg(test)]
 mod tests {
     use super::Html;
+    use super::HtmlParseOptions;
     use super::Selector;
+    use html5ever::tree_builder::QuirksMode;
+
+    #[test]
+    fn parse_detects_document() {
+        for html in [
+            "<!doctype html><p>a</p>",
+            "<HTML><p>a</p></HTML>",
+            "<meta charset=utf-8><body class=x>a</body>",
+        ] {
+            assert!(
+                Html::parse(html).tree.root().value().is_document(),
+                "{}",
+                html
+            );
+        }
+
+        for html in [
+            "<span>a</span>",
+            "<header>a</header>",
+            "<p>html body</p>",
+            "",
+        ] {
+            assert!(
+                Html::parse(html).tree.root().value().is_fragment(),
+                "{}",
+                html
+            );
+        }
+    }
 
     #[test]
     fn root_element_fragment() {
@@ -227,6 +817,291 @@ mod tests {
         assert_eq!(upper, Some(10));
     }
 
+    #[test]
+    fn select_excluding_skips_nested_subtrees() {
+        let html = Html::parse_document(
+            r#"<a id="1"></a>
+            <div class="footer"><a id="2"></a><div class="footer"><a id="3"></a></div><a id="4"></a></div>
+            <a id="5" class="footer"></a><a id="6"></a>"#,
+        );
+        let include = Selector::parse("a").unwrap();
+        let exclude = Selector::parse(".footer").unwrap();
+        let ids: Vec<_> = html
+            .select_excluding(&include, &exclude)
+            .map(|a| a.value().id().unwrap())
+            .collect();
+        assert_eq!(ids, ["1", "6"]);
+    }
+
+    #[test]
+    fn select_map_collects_in_document_order() {
+        let html = Html::parse_document(r#"<a href="/1">1</a><p><a>2</a></p><a href="/3">3</a>"#);
+        let hrefs = html.select_map(&Selector::parse("a[href]").unwrap(), |a| {
+            a.attr("href").unwrap().to_owned()
+        });
+        assert_eq!(hrefs, ["/1", "/3"]);
+    }
+
+    #[test]
+    fn select_text_eq_is_exact() {
+        let html = Html::parse_document(
+            "<button>Submit Order</button><button><b>Sub</b>mit</button><button>\n Submit\n</button>",
+        );
+        let button = Selector::parse("button").unwrap();
+
+        let trimmed: Vec<_> = html
+            .select_text_eq(&button, "Submit", true)
+            .map(|e| e.inner_html())
+            .collect();
+        assert_eq!(trimmed, ["<b>Sub</b>mit", "\n Submit\n"]);
+
+        let exact: Vec<_> = html
+            .select_text_eq(&button, "Submit", false)
+            .map(|e| e.inner_html())
+            .collect();
+        assert_eq!(exact, ["<b>Sub</b>mit"]);
+    }
+
+    #[test]
+    fn select_classes_matches_all() {
+        let html = Html::parse_document(
+            r#"<div class="a b c">1</div><div class="a c">2</div><span class="c b a x">3</span>"#,
+        );
+        let texts: Vec<_> = html
+            .select_classes(&["a", "b", "c"])
+            .map(|e| e.inner_html())
+            .collect();
+        assert_eq!(texts, ["1", "3"]);
+        assert_eq!(html.select_classes(&["a b"]).count(), 0);
+    }
+
+    #[test]
+    fn select_into_reuses_buffer() {
+        let html =
+            Html::parse_document("<ul><li>1</li><li>2</li><li>3</li></ul><ol><li>4</li></ol>");
+        let mut buf = Vec::new();
+
+        html.select_into(&Selector::parse("ul > li").unwrap(), &mut buf);
+        assert_eq!(buf.len(), 3);
+        let capacity = buf.capacity();
+
+        html.select_into(&Selector::parse("ol > li").unwrap(), &mut buf);
+        let texts: Vec<_> = buf.iter().map(|e| e.inner_html()).collect();
+        assert_eq!(texts, ["4"]);
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[test]
+    fn first_text_falls_back() {
+        let html = Html::parse_document(
+            r#"<h1> </h1><meta property="og:title" content="Meta"><div class="title"><b>Site</b> title</div>"#,
+        );
+        let h1 = Selector::parse("h1").unwrap();
+        let h2 = Selector::parse("h2").unwrap();
+        let title = Selector::parse(".title").unwrap();
+
+        assert_eq!(
+            html.first_text(&[&h2, &h1, &title]).as_deref(),
+            Some("Site title")
+        );
+        assert_eq!(html.first_text(&[&h2, &h1]), None);
+        assert_eq!(html.first_text(&[]), None);
+    }
+
+    #[test]
+    fn write_select_concatenates_html() {
+        let html = Html::parse_document(
+            r#"<ul><li class="a">1 &amp; 2</li><li><b>3</b></li></ul><ol><li>4</li></ol>"#,
+        );
+        let li = Selector::parse("li").unwrap();
+
+        let mut out = Vec::new();
+        html.write_select(&li, &mut out).unwrap();
+        let expected: String = html.select(&li).map(|e| e.html()).collect();
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn select_union_in_document_order() {
+        let html = Html::parse_document(
+            r#"<button class="btn">1</button><a>2</a><p>3</p><a class="btn">4</a>"#,
+        );
+        let a = Selector::parse("a").unwrap();
+        let btn = Selector::parse(".btn").unwrap();
+
+        let texts: Vec<_> = html
+            .select_union(&[&a, &btn])
+            .map(|e| e.inner_html())
+            .collect();
+        assert_eq!(texts, ["1", "2", "4"]);
+        assert_eq!(html.select_union(&[]).count(), 0);
+    }
+
+    #[test]
+    fn noscript_parsing_follows_scripting_option() {
+        let source = r#"<body><noscript><img src="pixel.gif"></noscript></body>"#;
+        let img = Selector::parse("noscript > img").unwrap();
+        let noscript = Selector::parse("noscript").unwrap();
+
+        let enabled = Html::parse_document(source);
+        assert!(!enabled.exists(&img));
+        let text: Vec<_> = enabled
+            .select(&noscript)
+            .flat_map(|noscript| noscript.text())
+            .collect();
+        assert_eq!(text, [r#"<img src="pixel.gif">"#]);
+
+        let options = HtmlParseOptions {
+            scripting_enabled: false,
+        };
+        let disabled = Html::parse_document_with_options(source, options);
+        assert!(disabled.exists(&img));
+
+        let fragment = Html::parse_fragment_with_options("<noscript><b>x</b></noscript>", options);
+        assert!(fragment.exists(&Selector::parse("noscript > b").unwrap()));
+    }
+
+    #[test]
+    fn select_data_by_key_and_value() {
+        let fragment = Html::parse_fragment(
+            r#"<form data-testid="login">
+                <input data-testid="user">
+                <button data-testid="submit" data-variant="">Log in</button>
+                <a data-test-id="submit">Help</a>
+            </form>"#,
+        );
+        let names = |key: &str, value: Option<&str>| {
+            fragment
+                .select_data(key, value)
+                .map(|element| element.value().name().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("testid", Some("submit")), ["button"]);
+        assert_eq!(names("testId", None), ["form", "input", "button"]);
+        assert_eq!(names("test-id", None), ["a"]);
+        assert_eq!(names("variant", Some("")), ["button"]);
+        assert_eq!(names("testid", Some("Submit")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reroot_at_element() {
+        let document = Html::parse_document(
+            r#"<aside><p>Sidebar</p></aside>
+            <div class="article"><h1>Title</h1><p>One</p><section><p>Two</p></section></div>
+            <p>Footer</p>"#,
+        );
+        let article = document
+            .select(&Selector::parse(".article").unwrap())
+            .next()
+            .unwrap();
+
+        let rerooted = document.reroot(article);
+        assert!(rerooted.tree.root().value().is_fragment());
+        assert_eq!(rerooted.root_element().html(), article.html());
+        let paragraphs: Vec<_> = rerooted
+            .select(&Selector::parse("p").unwrap())
+            .map(|p| p.inner_html())
+            .collect();
+        assert_eq!(paragraphs, ["One", "Two"]);
+    }
+
+    #[test]
+    fn exists_detects_page_variant() {
+        let html = Html::parse_document(r#"<form><div class="captcha"></div></form>"#);
+        assert!(html.exists(&Selector::parse("form .captcha").unwrap()));
+        assert!(!html.exists(&Selector::parse("form .login").unwrap()));
+    }
+
+    #[test]
+    fn tag_names_are_distinct() {
+        let html = Html::parse_document("<p>1</p><p>2<a>3</a></p>");
+        let names: Vec<_> = html.tag_names().into_iter().collect();
+        assert_eq!(names, ["a", "body", "head", "html", "p"]);
+    }
+
+    #[test]
+    fn duplicate_ids_in_order() {
+        let html = Html::parse_document(
+            r#"<div id="b">1</div><div id="x">2</div><p id="x">3</p><p id="b">4</p><p id="b">5</p><p id="c">6</p>"#,
+        );
+        let duplicates: Vec<_> = html
+            .duplicate_ids()
+            .into_iter()
+            .map(|(id, elements)| {
+                let texts: Vec<_> = elements.iter().map(|e| e.text().next().unwrap()).collect();
+                (id, texts)
+            })
+            .collect();
+        assert_eq!(
+            duplicates,
+            [
+                ("b".to_owned(), vec!["1", "4", "5"]),
+                ("x".to_owned(), vec!["2", "3"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn index_by_data_attribute() {
+        let html = Html::parse_document(
+            r#"<div data-id="a">1</div><p data-id="b">2</p><span data-id="a">3</span><i>4</i>"#,
+        );
+        let index = html.index_by_attr("data-id");
+        assert_eq!(index.len(), 2);
+
+        let a: Vec<_> = index["a"].iter().map(|e| e.inner_html()).collect();
+        assert_eq!(a, ["1", "3"]);
+        assert_eq!(index["b"][0].value().name(), "p");
+    }
+
+    #[test]
+    fn display_is_serialized_document() {
+        let html = Html::parse_document("<!DOCTYPE html><title>abc</title>");
+        assert_eq!(html.to_string(), html.html());
+    }
+
+    #[test]
+    fn count_many_matches_individual_counts() {
+        let html = Html::parse_document(
+            r#"<nav><a>1</a><a class="btn">2</a></nav><p><a>3</a></p><button class="btn"></button>"#,
+        );
+        let selectors = [
+            ("links", Selector::parse("a").unwrap()),
+            ("buttons", Selector::parse(".btn").unwrap()),
+            ("tables", Selector::parse("table").unwrap()),
+        ];
+        let named: Vec<_> = selectors.iter().map(|(n, s)| (*n, s)).collect();
+
+        let counts = html.count_many(&named);
+        assert_eq!(counts.len(), 3);
+        for (name, selector) in &selectors {
+            assert_eq!(counts[*name], html.select(selector).count());
+        }
+        assert_eq!(counts["tables"], 0);
+    }
+
+    #[test]
+    fn with_doctype_replaces_existing() {
+        let html = Html::parse_document(
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD HTML 4.01//EN"><title>abc</title>"#,
+        );
+        assert_eq!(
+            html.with_doctype("html"),
+            "<!DOCTYPE html><html><head><title>abc</title></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn with_doctype_inserts_into_quirks_document() {
+        let html = Html::parse_document("<!-- legacy --><p>abc</p>");
+        assert_eq!(html.quirks_mode, QuirksMode::Quirks);
+        assert_eq!(
+            html.with_doctype("html"),
+            "<!DOCTYPE html><!-- legacy --><html><head></head><body><p>abc</p></body></html>"
+        );
+    }
+
     #[cfg(feature = "atomic")]
     #[test]
     fn html_is_send() {
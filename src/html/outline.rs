@@ -0,0 +1,141 @@
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static HEADINGS: Lazy<Selector> = Lazy::new(|| Selector::parse("h1, h2, h3, h4, h5, h6").unwrap());
+
+impl Html {
+    /// Splits the document into sections introduced by headings matching a selector.
+    ///
+    /// Each heading is paired with its following sibling elements, up to the next matching
+    /// heading of the same or a higher level. Matching elements which are not `<h1>` to `<h6>`
+    /// are treated as level 6 headings. Lower level headings are included in the section and
+    /// also start their own.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <h2>Intro</h2><p>Hello</p>
+    ///     <h2>Usage</h2><p>Run</p><p>Stop</p>
+    /// "#);
+    /// let headings = Selector::parse("h2").unwrap();
+    ///
+    /// let sections = document.sections(&headings);
+    /// assert_eq!(sections[1].0.inner_html(), "Usage");
+    /// assert_eq!(sections[1].1.len(), 2);
+    /// ```
+    pub fn sections(
+        &self,
+        heading_selector: &Selector,
+    ) -> Vec<(ElementRef<'_>, Vec<ElementRef<'_>>)> {
+        let level = |element: &ElementRef| element.heading_level().unwrap_or(6);
+
+        self.select(heading_selector)
+            .map(|heading| {
+                let content = heading
+                    .next_siblings()
+                    .filter_map(ElementRef::wrap)
+                    .take_while(|sibling| {
+                        !(heading_selector.matches(sibling) && level(sibling) <= level(&heading))
+                    })
+                    .collect();
+                (heading, content)
+            })
+            .collect()
+    }
+
+    /// Returns the level, text and element of each `<h1>` to `<h6>` heading, in document order.
+    ///
+    /// Whitespace in the text is collapsed and trimmed.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document("<h1>Guide</h1><h2>Install</h2><h3>From  source</h3>");
+    ///
+    /// let outline: Vec<_> = document
+    ///     .outline()
+    ///     .into_iter()
+    ///     .map(|(level, text, _)| (level, text))
+    ///     .collect();
+    /// assert_eq!(outline[2], (3, "From source".to_owned()));
+    /// ```
+    pub fn outline(&self) -> Vec<(u8, String, ElementRef<'_>)> {
+        self.select(&HEADINGS)
+            .filter_map(|heading| {
+                let level = heading.heading_level()?;
+                let text = heading
+                    .text()
+                    .flat_map(str::split_whitespace)
+                    .collect::<Vec<_>>();
+                Some((level, text.join(" "), heading))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ElementRef, Html, Selector};
+
+    #[test]
+    fn sections_by_heading_level() {
+        fn text<'a>(element: ElementRef<'a>) -> &'a str {
+            element.text().next().unwrap()
+        }
+
+        let html = Html::parse_document(
+            r#"<h1>Title</h1>
+            <h2>A</h2><p>a1</p><p>a2</p>
+            <h3>A.1</h3><p>a3</p>
+            <h2>B</h2><p>b1</p>"#,
+        );
+        let sections: Vec<_> = html
+            .sections(&Selector::parse("h1, h2, h3").unwrap())
+            .into_iter()
+            .map(|(heading, content)| {
+                (
+                    text(heading),
+                    content.into_iter().map(text).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                ("Title", vec!["A", "a1", "a2", "A.1", "a3", "B", "b1"]),
+                ("A", vec!["a1", "a2", "A.1", "a3"]),
+                ("A.1", vec!["a3"]),
+                ("B", vec!["b1"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_levels_in_document_order() {
+        let html = Html::parse_document(
+            r#"<h1>Title</h1>
+            <section><h2>First <em>part</em></h2><p>text</p></section>
+            <h2>
+                Second
+            </h2>
+            <h3>Detail</h3>"#,
+        );
+        let outline: Vec<_> = html
+            .outline()
+            .into_iter()
+            .map(|(level, text, heading)| (level, text, heading.value().name().to_owned()))
+            .collect();
+        assert_eq!(
+            outline,
+            [
+                (1, "Title".to_owned(), "h1".to_owned()),
+                (2, "First part".to_owned(), "h2".to_owned()),
+                (2, "Second".to_owned(), "h2".to_owned()),
+                (3, "Detail".to_owned(), "h3".to_owned()),
+            ]
+        );
+    }
+}
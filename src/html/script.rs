@@ -0,0 +1,185 @@
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static SCRIPT: Lazy<Selector> = Lazy::new(|| Selector::parse("script").unwrap());
+
+/// A `<script>` element of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptInfo<'a> {
+    /// The `<script>` element.
+    pub element: ElementRef<'a>,
+
+    /// The `src` attribute of an external script.
+    pub src: Option<&'a str>,
+
+    /// The `type` attribute, e.g. `module` or `application/ld+json`.
+    pub script_type: Option<&'a str>,
+
+    /// The inline content of the script.
+    pub inline: Option<&'a str>,
+}
+
+impl Html {
+    /// Returns an iterator over the scripts of the document.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <script src="/app.js" type="module"></script>
+    ///     <script id="__NEXT_DATA__" type="application/json">{"page":"/"}</script>
+    /// "#);
+    /// let scripts: Vec<_> = document.scripts().collect();
+    ///
+    /// assert_eq!(scripts[0].src, Some("/app.js"));
+    /// assert_eq!(scripts[1].inline, Some(r#"{"page":"/"}"#));
+    /// ```
+    pub fn scripts(&self) -> impl Iterator<Item = ScriptInfo<'_>> {
+        self.select(&SCRIPT).map(|element| ScriptInfo {
+            element,
+            src: element.attr("src"),
+            script_type: element.attr("type"),
+            inline: element.text().next(),
+        })
+    }
+
+    /// Finds an assignment of a JSON object or array to a variable in the inline scripts, such
+    /// as `window.__DATA__ = {...};`, and parses the value.
+    ///
+    /// The value must be a JSON literal; assignments of JavaScript object literals which are not
+    /// valid JSON are skipped.
+    ///
+    /// ```
+    /// use scraper::Html;
+    /// use serde_json::json;
+    ///
+    /// let document = Html::parse_document(r#"
+    ///     <script>window.__DATA__ = {"user": {"id": 7}};</script>
+    /// "#);
+    ///
+    /// assert_eq!(document.script_json("__DATA__"), Some(json!({"user": {"id": 7}})));
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn script_json(&self, var_name: &str) -> Option<serde_json::Value> {
+        self.scripts()
+            .filter(|script| script.src.is_none())
+            .filter_map(|script| script.inline)
+            .find_map(|script| {
+                assigned_literals(script, var_name)
+                    .find_map(|literal| serde_json::from_str(literal).ok())
+            })
+    }
+}
+
+/// Returns the balanced object or array literals assigned to a variable in a script.
+#[cfg(feature = "serde_json")]
+fn assigned_literals<'a>(script: &'a str, var_name: &'a str) -> impl Iterator<Item = &'a str> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    script
+        .match_indices(var_name)
+        .filter_map(move |(start, _)| {
+            if script[..start].chars().next_back().is_some_and(is_ident) {
+                return None;
+            }
+            let rest = &script[start + var_name.len()..];
+            if rest.chars().next().is_some_and(is_ident) {
+                return None;
+            }
+            let rest = rest.trim_start().strip_prefix('=')?;
+            if rest.starts_with(['=', '>']) {
+                return None;
+            }
+            balanced(rest.trim_start())
+        })
+}
+
+/// Returns the object or array literal at the start of the text, matching brackets outside of
+/// strings.
+#[cfg(feature = "serde_json")]
+fn balanced(text: &str) -> Option<&str> {
+    if !text.starts_with(['{', '[']) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn external_and_inline_scripts() {
+        let html = Html::parse_document(
+            r#"<head><script src="https://cdn.example.com/lib.js"></script></head>
+            <body><script type="application/json">{"a": 1 < 2}</script></body>"#,
+        );
+        let scripts: Vec<_> = html
+            .scripts()
+            .map(|script| (script.src, script.script_type, script.inline))
+            .collect();
+        assert_eq!(
+            scripts,
+            [
+                (Some("https://cdn.example.com/lib.js"), None, None),
+                (None, Some("application/json"), Some(r#"{"a": 1 < 2}"#)),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn script_json_assignment() {
+        use serde_json::json;
+
+        let html = Html::parse_document(
+            r#"<script src="/app.js">window.__NEXT_DATA__ = {"external": true};</script>
+            <script>
+                var my__NEXT_DATA__ = {"wrong": true};
+                if (window.__NEXT_DATA__ == null) {}
+                window.__NEXT_DATA__ = {notJson: 1};
+                window.__NEXT_DATA__ = {
+                    "props": {"title": "a } b", "tags": ["x", "y\"]"]},
+                    "page": "/"
+                };
+                window.__OTHER__=[1, 2];
+            </script>"#,
+        );
+        assert_eq!(
+            html.script_json("__NEXT_DATA__"),
+            Some(json!({
+                "props": {"title": "a } b", "tags": ["x", "y\"]"]},
+                "page": "/"
+            }))
+        );
+        assert_eq!(html.script_json("__OTHER__"), Some(json!([1, 2])));
+        assert_eq!(html.script_json("__MISSING__"), None);
+    }
+}
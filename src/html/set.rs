@@ -0,0 +1,99 @@
+use std::iter::FromIterator;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+/// A collection of HTML documents which can be queried as one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlSet {
+    documents: Vec<Html>,
+}
+
+impl HtmlSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a document to the set.
+    pub fn push(&mut self, document: Html) {
+        self.documents.push(document);
+    }
+
+    /// Returns the documents in the set.
+    pub fn documents(&self) -> &[Html] {
+        &self.documents
+    }
+
+    /// Returns an iterator over elements matching a selector in all documents.
+    ///
+    /// Each element is tagged with the index of its document in the set.
+    ///
+    /// ```
+    /// use scraper::html::HtmlSet;
+    /// use scraper::{Html, Selector};
+    ///
+    /// let set: HtmlSet = ["<p>a</p>", "<p>b</p><p>c</p>"]
+    ///     .iter()
+    ///     .map(|html| Html::parse_fragment(html))
+    ///     .collect();
+    /// let selector = Selector::parse("p").unwrap();
+    ///
+    /// let indices: Vec<_> = set.select(&selector).map(|(index, _)| index).collect();
+    /// assert_eq!(indices, [0, 1, 1]);
+    /// ```
+    pub fn select<'a, 'b>(
+        &'a self,
+        selector: &'b Selector,
+    ) -> impl Iterator<Item = (usize, ElementRef<'a>)> + 'b
+    where
+        'a: 'b,
+    {
+        self.documents
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, document)| {
+                document
+                    .select(selector)
+                    .map(move |element| (index, element))
+            })
+    }
+}
+
+impl From<Vec<Html>> for HtmlSet {
+    fn from(documents: Vec<Html>) -> Self {
+        HtmlSet { documents }
+    }
+}
+
+impl FromIterator<Html> for HtmlSet {
+    fn from_iter<I: IntoIterator<Item = Html>>(iter: I) -> Self {
+        HtmlSet {
+            documents: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlSet;
+    use crate::{Html, Selector};
+
+    #[test]
+    fn select_across_fragments() {
+        let mut set = HtmlSet::new();
+        set.push(Html::parse_fragment(
+            r#"<a href="/1"></a><a href="/2"></a>"#,
+        ));
+        set.push(Html::parse_fragment("<p>no links</p>"));
+        set.push(Html::parse_fragment(r#"<a href="/3"></a>"#));
+
+        let selector = Selector::parse("a").unwrap();
+        let matches: Vec<_> = set
+            .select(&selector)
+            .map(|(index, a)| (index, a.attr("href").unwrap()))
+            .collect();
+        assert_eq!(matches, [(0, "/1"), (0, "/2"), (2, "/3")]);
+        assert_eq!(set.documents().len(), 3);
+    }
+}
@@ -0,0 +1,125 @@
+use super::Html;
+use crate::Selector;
+
+/// A rule that the number of elements matching a selector is within bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructureRule {
+    /// The selector to count.
+    pub selector: Selector,
+
+    /// The minimum number of matches.
+    pub min: usize,
+
+    /// The maximum number of matches, if any.
+    pub max: Option<usize>,
+}
+
+impl StructureRule {
+    /// Requires exactly `count` matches.
+    pub fn exactly(selector: Selector, count: usize) -> Self {
+        StructureRule {
+            selector,
+            min: count,
+            max: Some(count),
+        }
+    }
+
+    /// Requires at least `min` matches.
+    pub fn at_least(selector: Selector, min: usize) -> Self {
+        StructureRule {
+            selector,
+            min,
+            max: None,
+        }
+    }
+
+    /// Allows at most `max` matches.
+    pub fn at_most(selector: Selector, max: usize) -> Self {
+        StructureRule {
+            selector,
+            min: 0,
+            max: Some(max),
+        }
+    }
+
+    fn allows(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+/// A [`StructureRule`] which a document does not satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    /// The index of the rule.
+    pub rule: usize,
+
+    /// The number of elements which matched the selector of the rule.
+    pub count: usize,
+}
+
+impl Html {
+    /// Checks the number of elements matching each rule's selector, returning the rules which
+    /// are violated.
+    ///
+    /// ```
+    /// use scraper::html::{StructureRule, Violation};
+    /// use scraper::{Html, Selector};
+    ///
+    /// let document = Html::parse_document("<title>Page</title><h1>A</h1><h1>B</h1>");
+    /// let rules = [
+    ///     StructureRule::at_least(Selector::parse("title").unwrap(), 1),
+    ///     StructureRule::exactly(Selector::parse("h1").unwrap(), 1),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     document.validate_structure(&rules),
+    ///     [Violation { rule: 1, count: 2 }],
+    /// );
+    /// ```
+    pub fn validate_structure(&self, rules: &[StructureRule]) -> Vec<Violation> {
+        rules
+            .iter()
+            .enumerate()
+            .filter_map(|(rule, structure)| {
+                let count = self.select(&structure.selector).count();
+                (!structure.allows(count)).then_some(Violation { rule, count })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StructureRule, Violation};
+    use crate::{Html, Selector};
+
+    #[test]
+    fn structure_rules() {
+        let document = Html::parse_document(
+            r#"<title>T</title><main><h1>One</h1><h1>Two</h1><p>a</p><p>b</p><p>c</p></main>"#,
+        );
+        let rule = |selector: &str, min: usize, max: Option<usize>| StructureRule {
+            selector: Selector::parse(selector).unwrap(),
+            min,
+            max,
+        };
+        let rules = [
+            StructureRule::exactly(Selector::parse("h1").unwrap(), 1),
+            StructureRule::at_least(Selector::parse("title").unwrap(), 1),
+            StructureRule::at_least(Selector::parse("nav").unwrap(), 1),
+            StructureRule::at_most(Selector::parse("main p").unwrap(), 2),
+            rule("main", 1, Some(1)),
+            rule("p", 2, Some(5)),
+        ];
+
+        assert_eq!(
+            document.validate_structure(&rules),
+            [
+                Violation { rule: 0, count: 2 },
+                Violation { rule: 2, count: 0 },
+                Violation { rule: 3, count: 3 },
+            ]
+        );
+        assert_eq!(document.validate_structure(&[]), []);
+    }
+}
@@ -0,0 +1,176 @@
+use ego_tree::NodeRef;
+
+use super::Html;
+use crate::element_ref::is_display_hidden;
+use crate::{ElementRef, Node};
+
+impl Html {
+    /// Returns the rendered text of the document with one line per block, for line-based
+    /// diffing.
+    ///
+    /// Whitespace within a line is collapsed, and block-level elements and `<br>` start new
+    /// lines. Empty lines, hidden elements and elements which are never rendered, such as
+    /// `<head>` and `<script>`, are omitted. An element is hidden by the `hidden` attribute or an
+    /// inline `display: none` or `visibility: hidden` style, but not by `aria-hidden`. Pages
+    /// which differ only in formatting or inline markup produce the same text.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let document = Html::parse_document("<h1>Title</h1><p>Some\n  <b>bold</b> text</p>");
+    /// assert_eq!(document.normalized_document_text(), "Title\nSome bold text");
+    /// ```
+    pub fn normalized_document_text(&self) -> String {
+        let mut lines = Lines::default();
+        lines.children(self.tree.root());
+        lines.flush();
+        lines.lines.join("\n")
+    }
+}
+
+#[derive(Default)]
+struct Lines {
+    lines: Vec<String>,
+    current: String,
+}
+
+impl Lines {
+    fn children(&mut self, node: NodeRef<Node>) {
+        for child in node.children() {
+            match child.value() {
+                Node::Text(text) => self.current.push_str(text),
+                Node::Element(element) => {
+                    if ElementRef::wrap(child).is_some_and(is_display_hidden) {
+                        continue;
+                    }
+                    match element.name() {
+                        "head" | "script" | "style" | "template" | "noscript" => {}
+                        "br" => self.flush(),
+                        name if is_block(name) => {
+                            self.flush();
+                            self.children(child);
+                            self.flush();
+                        }
+                        _ => self.children(child),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        let line = self
+            .current
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !line.is_empty() {
+            self.lines.push(line);
+        }
+        self.current.clear();
+    }
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "body"
+            | "caption"
+            | "dd"
+            | "details"
+            | "dialog"
+            | "div"
+            | "dl"
+            | "dt"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "header"
+            | "hr"
+            | "html"
+            | "legend"
+            | "li"
+            | "main"
+            | "nav"
+            | "ol"
+            | "p"
+            | "pre"
+            | "section"
+            | "summary"
+            | "table"
+            | "tbody"
+            | "td"
+            | "tfoot"
+            | "th"
+            | "thead"
+            | "tr"
+            | "ul"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn formatting_differences_normalize_away() {
+        let compact = Html::parse_document(
+            "<html><head><title>Old</title><style>p{}</style></head><body>\
+             <h1>News</h1><div><p>First <a href=\"/a\">story</a>.</p><p>Second<br>line</p></div>\
+             <ul><li>One</li><li>Two</li></ul><script>track()</script></body></html>",
+        );
+        let formatted = Html::parse_document(
+            r#"<!DOCTYPE html>
+            <html>
+              <head><title>New</title></head>
+              <body>
+                <header><h1>
+                    News
+                </h1></header>
+                <section>
+                  <p>First <strong><a href="/b">story</a></strong>.</p>
+                  <div>Second<br/>
+                       line</div>
+                </section>
+                <p hidden>Draft</p>
+                <ol>
+                  <li>One</li>
+                  <li> Two </li>
+                </ol>
+              </body>
+            </html>"#,
+        );
+
+        let text = compact.normalized_document_text();
+        assert_eq!(text, "News\nFirst story.\nSecond\nline\nOne\nTwo");
+        assert_eq!(formatted.normalized_document_text(), text);
+    }
+
+    #[test]
+    fn hidden_elements_are_skipped() {
+        let document = Html::parse_document(
+            r#"<p>Shown</p>
+            <div style="display:none"><p>Modal</p></div>
+            <p style="color: red; visibility: hidden">Ghost</p>
+            <p>Price <span aria-hidden="true">$9.99</span><span hidden>nine dollars</span></p>
+            <p style="display: block">Also shown</p>"#,
+        );
+        assert_eq!(
+            document.normalized_document_text(),
+            "Shown\nPrice $9.99\nAlso shown"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+
+use once_cell::sync::Lazy;
+
+use super::Html;
+use crate::{ElementRef, Selector};
+
+static TIME: Lazy<Selector> = Lazy::new(|| Selector::parse("time").unwrap());
+
+impl Html {
+    /// Returns an iterator over `<time>` elements with their `datetime` attribute and text.
+    ///
+    /// The `datetime` attribute holds the machine-readable form of the human-readable text.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<time datetime="2020-01-02">Jan 2</time>"#);
+    /// let (_, datetime, text) = fragment.times().next().unwrap();
+    ///
+    /// assert_eq!(datetime, Some("2020-01-02"));
+    /// assert_eq!(text, "Jan 2");
+    /// ```
+    pub fn times(&self) -> impl Iterator<Item = (ElementRef<'_>, Option<&str>, Cow<'_, str>)> {
+        self.select(&TIME)
+            .map(|element| (element, element.attr("datetime"), element.text_contents()))
+    }
+}
+
+/// A typed value of the `datetime` attribute of a `<time>` element.
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeValue {
+    /// A date such as `2020-01-02`.
+    Date(NaiveDate),
+
+    /// A time such as `13:45:30`.
+    Time(NaiveTime),
+
+    /// A date and time without an offset such as `2020-01-02T13:45`.
+    LocalDateTime(NaiveDateTime),
+
+    /// A date and time with an offset such as `2020-01-02T13:45Z`.
+    DateTime(DateTime<FixedOffset>),
+}
+
+#[cfg(feature = "chrono")]
+impl TimeValue {
+    /// Parses a date, time or date and time string as used by the `datetime` attribute.
+    ///
+    /// Durations, week strings and other partial forms are not supported.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Some(TimeValue::Date(date));
+        }
+
+        for format in ["%H:%M:%S%.f", "%H:%M"] {
+            if let Ok(time) = NaiveTime::parse_from_str(value, format) {
+                return Some(TimeValue::Time(time));
+            }
+        }
+
+        // A space may be used in place of the `T` separator.
+        let value = value.replacen(' ', "T", 1);
+
+        for format in ["%Y-%m-%dT%H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M%#z"] {
+            if let Ok(datetime) = DateTime::parse_from_str(&value.replace('Z', "+00:00"), format) {
+                return Some(TimeValue::DateTime(datetime));
+            }
+        }
+
+        for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"] {
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(&value, format) {
+                return Some(TimeValue::LocalDateTime(datetime));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+
+    #[test]
+    fn times_with_machine_and_human_forms() {
+        let html = Html::parse_document(
+            r#"<p>Published <time datetime="2020-01-02">Jan <b>2</b></time></p><time>noon</time>"#,
+        );
+        let times: Vec<_> = html
+            .times()
+            .map(|(element, datetime, text)| (element.value().name(), datetime, text.into_owned()))
+            .collect();
+        assert_eq!(
+            times,
+            [
+                ("time", Some("2020-01-02"), "Jan 2".to_owned()),
+                ("time", None, "noon".to_owned()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn time_value_parsing() {
+        use super::TimeValue;
+        use chrono::{NaiveDate, NaiveTime};
+
+        let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(13, 45, 0).unwrap();
+
+        assert_eq!(TimeValue::parse("2020-01-02"), Some(TimeValue::Date(date)));
+        assert_eq!(TimeValue::parse("13:45"), Some(TimeValue::Time(time)));
+        assert_eq!(
+            TimeValue::parse("2020-01-02 13:45"),
+            Some(TimeValue::LocalDateTime(date.and_time(time)))
+        );
+        match TimeValue::parse("2020-01-02T13:45:00+02:00") {
+            Some(TimeValue::DateTime(datetime)) => {
+                assert_eq!(datetime.naive_local(), date.and_time(time));
+                assert_eq!(datetime.offset().local_minus_utc(), 2 * 3600);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            TimeValue::parse("2020-01-02T13:45Z"),
+            Some(TimeValue::DateTime(_))
+        ));
+        assert_eq!(TimeValue::parse("P2D"), None);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use super::Html;
+use crate::node::Text;
+use crate::{Node, Selector};
+
+impl Html {
+    /// Unwraps redundant wrapper elements.
+    ///
+    /// A `<div>` or `<span>` without attributes whose only child, ignoring whitespace-only text,
+    /// is an element is replaced by that child. Nested wrappers collapse repeatedly.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let mut fragment = Html::parse_fragment(r#"<div><div> <p class="x">Hi</p> </div></div>"#);
+    /// fragment.collapse_wrappers();
+    ///
+    /// assert_eq!(fragment.root_element().inner_html(), r#"<p class="x">Hi</p>"#);
+    /// ```
+    pub fn collapse_wrappers(&mut self) {
+        let ids: Vec<_> = self
+            .tree
+            .root()
+            .descendants()
+            .map(|node| node.id())
+            .collect();
+
+        for id in ids {
+            let node = self.tree.get(id).unwrap();
+            let is_wrapper = match node.value() {
+                Node::Element(element) => {
+                    matches!(element.name(), "div" | "span") && element.attrs.is_empty()
+                }
+                _ => false,
+            };
+            if !is_wrapper {
+                continue;
+            }
+
+            let mut children = node.children().filter(
+                |child| !matches!(child.value(), Node::Text(text) if text.trim().is_empty()),
+            );
+            let child = match (children.next(), children.next()) {
+                (Some(child), None) if child.value().is_element() => child.id(),
+                _ => continue,
+            };
+
+            let mut wrapper = self.tree.get_mut(id).unwrap();
+            wrapper.insert_id_before(child);
+            wrapper.detach();
+        }
+    }
+
+    /// Rewrites the text nodes of the document.
+    ///
+    /// Each text node is replaced by the string returned by `f`, or removed if it is empty.
+    /// Text nodes for which `f` returns `None` are left unchanged.
+    ///
+    /// ```
+    /// use scraper::Html;
+    ///
+    /// let mut fragment = Html::parse_fragment("<p>Call 555-0100</p>");
+    /// fragment.map_text(|text| Some(text.replace("555-0100", "[redacted]")));
+    ///
+    /// assert_eq!(fragment.root_element().inner_html(), "<p>Call [redacted]</p>");
+    /// ```
+    pub fn map_text<F: FnMut(&str) -> Option<String>>(&mut self, mut f: F) {
+        let ids: Vec<_> = self
+            .tree
+            .root()
+            .descendants()
+            .filter(|node| node.value().is_text())
+            .map(|node| node.id())
+            .collect();
+
+        for id in ids {
+            let mut node = self.tree.get_mut(id).unwrap();
+            let replacement = match node.value() {
+                Node::Text(text) => f(text),
+                _ => None,
+            };
+            match replacement {
+                Some(text) if text.is_empty() => node.detach(),
+                Some(text) => {
+                    *node.value() = Node::Text(Text {
+                        text: text.as_str().into(),
+                    })
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Removes everything except the elements matching a selector, their descendants and their
+    /// ancestors.
+    ///
+    /// Text and comments outside the matched elements are removed as well. The doctype and the
+    /// root element are always kept.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let mut fragment = Html::parse_fragment(
+    ///     r#"<nav>Menu</nav><main><p class="article">Text</p><aside>Ads</aside></main>"#,
+    /// );
+    /// fragment.prune_to(&Selector::parse(".article").unwrap());
+    ///
+    /// assert_eq!(
+    ///     fragment.root_element().inner_html(),
+    ///     r#"<main><p class="article">Text</p></main>"#,
+    /// );
+    /// ```
+    pub fn prune_to(&mut self, keep: &Selector) {
+        let mut matched = HashSet::new();
+        let mut kept = HashSet::new();
+        kept.insert(self.root_element().id());
+        for element in self.select(keep) {
+            matched.insert(element.id());
+            kept.extend(element.ancestors().map(|ancestor| ancestor.id()));
+        }
+
+        let mut removed = Vec::new();
+        let mut stack = vec![self.tree.root()];
+        while let Some(node) = stack.pop() {
+            for child in node.children() {
+                if matched.contains(&child.id()) || child.value().is_doctype() {
+                    continue;
+                }
+                if kept.contains(&child.id()) {
+                    stack.push(child);
+                } else {
+                    removed.push(child.id());
+                }
+            }
+        }
+
+        for id in removed {
+            self.tree.get_mut(id).unwrap().detach();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Html, Selector};
+
+    #[test]
+    fn nested_wrappers_collapse() {
+        let mut fragment = Html::parse_fragment(
+            r#"<section><div><div><div><div class="card">x</div></div></div></div></section>
+            <div><p>a</p><p>b</p></div>
+            <div>text<b>c</b></div>
+            <span><!-- c --><i>d</i></span>"#,
+        );
+        fragment.collapse_wrappers();
+        assert_eq!(
+            fragment.root_element().inner_html(),
+            r#"<section><div class="card">x</div></section>
+            <div><p>a</p><p>b</p></div>
+            <div>text<b>c</b></div>
+            <span><!-- c --><i>d</i></span>"#
+        );
+    }
+
+    #[test]
+    fn map_text_rewrites_and_removes() {
+        let mut html = Html::parse_fragment(
+            "<p>Hello <b>world</b></p><!-- comment --><script>keep()</script><p>drop</p>",
+        );
+        html.map_text(|text| match text {
+            "drop" => Some(String::new()),
+            "keep()" => None,
+            text => Some(text.to_uppercase()),
+        });
+        assert_eq!(
+            html.root_element().inner_html(),
+            "<p>HELLO <b>WORLD</b></p><!-- comment --><script>keep()</script><p></p>"
+        );
+
+        let before = html.root_element().inner_html();
+        html.map_text(|_| None);
+        assert_eq!(html.root_element().inner_html(), before);
+    }
+
+    #[test]
+    fn prune_to_keeps_matches_and_their_path() {
+        let mut document = Html::parse_document(
+            r#"<!DOCTYPE html><html><head><title>T</title></head><body>
+            <div class="sidebar"><p>Links</p></div>
+            <div id="content">
+                <h1>Heading</h1>
+                <div class="article"><p>One <b>bold</b></p><!-- note --></div>
+                <div class="article"><p>Two</p></div>
+            </div>
+            <footer>Footer</footer>
+            </body></html>"#,
+        );
+        document.prune_to(&Selector::parse(".article").unwrap());
+        assert_eq!(
+            document.html(),
+            concat!(
+                "<!DOCTYPE html><html><body><div id=\"content\">",
+                "<div class=\"article\"><p>One <b>bold</b></p><!-- note --></div>",
+                "<div class=\"article\"><p>Two</p></div>",
+                "</div></body></html>"
+            )
+        );
+
+        document.prune_to(&Selector::parse("table").unwrap());
+        assert_eq!(document.html(), "<!DOCTYPE html><html></html>");
+    }
+}
@@ -0,0 +1,157 @@
+use ego_tree::NodeRef;
+use serde_json::{json, Map, Value};
+
+use super::Html;
+use crate::Node;
+
+impl Html {
+    /// Converts the document into a JSON tree with a stable schema.
+    ///
+    /// Every node is an object with a `type` of `document`, `fragment`, `doctype`, `comment`,
+    /// `text`, `element` or `processing_instruction`:
+    ///
+    /// - `document` and `fragment` nodes have `children`.
+    /// - `doctype` nodes have `name`, `public_id` and `system_id`.
+    /// - `comment` and `text` nodes have `text`.
+    /// - `element` nodes have `name`, `namespace`, `attributes` (an object mapping qualified
+    ///   names such as `xlink:href` to values, sorted by name) and `children`.
+    /// - `processing_instruction` nodes have `target` and `data`.
+    ///
+    /// The contents of a `<template>` element are its single `fragment` child.
+    ///
+    /// ```
+    /// use scraper::Html;
+    /// use serde_json::json;
+    ///
+    /// let fragment = Html::parse_fragment(r#"<a href="/">Home</a>"#);
+    /// assert_eq!(
+    ///     fragment.to_tree_json()["children"][0]["children"][0],
+    ///     json!({
+    ///         "type": "element",
+    ///         "name": "a",
+    ///         "namespace": "http://www.w3.org/1999/xhtml",
+    ///         "attributes": { "href": "/" },
+    ///         "children": [{ "type": "text", "text": "Home" }],
+    ///     })
+    /// );
+    /// ```
+    pub fn to_tree_json(&self) -> Value {
+        node_json(self.tree.root())
+    }
+}
+
+fn node_json(node: NodeRef<Node>) -> Value {
+    let children = || Value::Array(node.children().map(node_json).collect());
+
+    match node.value() {
+        Node::Document => json!({ "type": "document", "children": children() }),
+        Node::Fragment => json!({ "type": "fragment", "children": children() }),
+        Node::Doctype(doctype) => json!({
+            "type": "doctype",
+            "name": doctype.name(),
+            "public_id": doctype.public_id(),
+            "system_id": doctype.system_id(),
+        }),
+        Node::Comment(comment) => json!({ "type": "comment", "text": &**comment }),
+        Node::Text(text) => json!({ "type": "text", "text": &**text }),
+        Node::Element(element) => {
+            // Sort explicitly, as `Map` keeps insertion order with serde_json's `preserve_order`.
+            let mut attrs: Vec<_> = element
+                .attrs
+                .iter()
+                .map(|(name, value)| {
+                    let name = match &name.prefix {
+                        Some(prefix) => format!("{}:{}", prefix, name.local),
+                        None => name.local.to_string(),
+                    };
+                    (name, Value::from(&**value))
+                })
+                .collect();
+            attrs.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            let attributes: Map<_, _> = attrs.into_iter().collect();
+            json!({
+                "type": "element",
+                "name": element.name(),
+                "namespace": &*element.name.ns,
+                "attributes": attributes,
+                "children": children(),
+            })
+        }
+        Node::ProcessingInstruction(pi) => json!({
+            "type": "processing_instruction",
+            "target": &*pi.target,
+            "data": &*pi.data,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Html;
+    use serde_json::json;
+
+    #[test]
+    fn tree_json_of_fragment() {
+        let fragment =
+            Html::parse_fragment(r#"<p id="x" class="y">Hi<!-- c --><br></p><svg></svg>"#);
+        assert_eq!(
+            fragment.to_tree_json(),
+            json!({
+                "type": "fragment",
+                "children": [{
+                    "type": "element",
+                    "name": "html",
+                    "namespace": "http://www.w3.org/1999/xhtml",
+                    "attributes": {},
+                    "children": [
+                        {
+                            "type": "element",
+                            "name": "p",
+                            "namespace": "http://www.w3.org/1999/xhtml",
+                            "attributes": { "class": "y", "id": "x" },
+                            "children": [
+                                { "type": "text", "text": "Hi" },
+                                { "type": "comment", "text": " c " },
+                                {
+                                    "type": "element",
+                                    "name": "br",
+                                    "namespace": "http://www.w3.org/1999/xhtml",
+                                    "attributes": {},
+                                    "children": [],
+                                },
+                            ],
+                        },
+                        {
+                            "type": "element",
+                            "name": "svg",
+                            "namespace": "http://www.w3.org/2000/svg",
+                            "attributes": {},
+                            "children": [],
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn tree_json_attributes_use_sorted_qualified_names() {
+        let fragment = Html::parse_fragment(
+            r##"<svg><a xlink:href="#x" href="#y" id="z" class="w"></a></svg>"##,
+        );
+        let a = &fragment.to_tree_json()["children"][0]["children"][0]["children"][0];
+        let names: Vec<_> = a["attributes"].as_object().unwrap().keys().collect();
+        assert_eq!(names, ["class", "href", "id", "xlink:href"]);
+        assert_eq!(a["attributes"]["xlink:href"], "#x");
+        assert_eq!(a["attributes"]["href"], "#y");
+    }
+
+    #[test]
+    fn tree_json_of_doctype() {
+        let document = Html::parse_document("<!DOCTYPE html><title>t</title>");
+        assert_eq!(
+            document.to_tree_json()["children"][0],
+            json!({ "type": "doctype", "name": "html", "public_id": "", "system_id": "" })
+        );
+    }
+}
@@ -43,7 +43,7 @@ impl TreeSink for Html {
     // What is the name of this element?
     //
     // Should never be called on a non-element node; feel free to panic!.
-    fn elem_name(&self, target: &Self::Handle) -> ExpandedName {
+    fn elem_name(&self, target: &Self::Handle) -> ExpandedName<'_> {
         self.tree
             .get(*target)
             .unwrap()
@@ -118,7 +118,7 @@ impl TreeSink for Html {
             NodeOrText::AppendText(text) => {
                 let text = make_tendril(text);
 
-                let did_concat = parent.last_child().map_or(false, |mut n| match n.value() {
+                let did_concat = parent.last_child().is_some_and(|mut n| match n.value() {
                     Node::Text(t) => {
                         t.text.push_tendril(&text);
                         true
@@ -160,16 +160,13 @@ impl TreeSink for Html {
                 NodeOrText::AppendText(text) => {
                     let text = make_tendril(text);
 
-                    let did_concat =
-                        sibling
-                            .prev_sibling()
-                            .map_or(false, |mut n| match n.value() {
-                                Node::Text(t) => {
-                                    t.text.push_tendril(&text);
-                                    true
-                                }
-                                _ => false,
-                            });
+                    let did_concat = sibling.prev_sibling().is_some_and(|mut n| match n.value() {
+                        Node::Text(t) => {
+                            t.text.push_tendril(&text);
+                            true
+                        }
+                        _ => false,
+                    });
 
                     if !did_concat {
                         sibling.insert_before(Node::Text(Text { text }));
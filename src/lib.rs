@@ -145,12 +145,14 @@ pub use crate::selector::Selector;
 
 pub use selectors::{attr::CaseSensitivity, Element};
 
+pub mod accessibility;
 pub mod element_ref;
 pub mod error;
 pub mod html;
 pub mod node;
 pub mod selectable;
 pub mod selector;
+pub mod stylesheet;
 
 #[cfg(feature = "atomic")]
 pub(crate) mod tendril_util {
@@ -275,7 +275,7 @@ impl Element {
     }
 
     /// Returns an iterator over the element's classes.
-    pub fn classes(&self) -> Classes {
+    pub fn classes(&self) -> Classes<'_> {
         let classes = self.classes.get_or_init(|| {
             let mut classes: Vec<LocalName> = self
                 .attrs
@@ -302,7 +302,7 @@ impl Element {
     }
 
     /// Returns an iterator over the element's attributes.
-    pub fn attrs(&self) -> Attrs {
+    pub fn attrs(&self) -> Attrs<'_> {
         Attrs {
             inner: self.attrs.iter(),
         }
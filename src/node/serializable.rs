@@ -2,6 +2,7 @@ use std::io::Error;
 
 use ego_tree::{iter::Edge, NodeRef};
 use html5ever::serialize::{Serializer, TraversalScope};
+use html5ever::QualName;
 
 use crate::Node;
 
@@ -10,11 +11,22 @@ pub(crate) fn serialize<S: Serializer>(
     self_node: NodeRef<Node>,
     serializer: &mut S,
     traversal_scope: TraversalScope,
+) -> Result<(), Error> {
+    serialize_filtered(self_node, serializer, traversal_scope, |_| true)
+}
+
+/// Serialize an HTML node using html5ever serializer, keeping only the attributes accepted by
+/// `keep_attr`.
+pub(crate) fn serialize_filtered<S: Serializer, F: Fn(&QualName) -> bool>(
+    self_node: NodeRef<Node>,
+    serializer: &mut S,
+    traversal_scope: TraversalScope,
+    keep_attr: F,
 ) -> Result<(), Error> {
     for edge in self_node.traverse() {
         match edge {
             Edge::Open(node) => {
-                if node == self_node && traversal_scope == TraversalScope::ChildrenOnly(None) {
+                if node == self_node && matches!(traversal_scope, TraversalScope::ChildrenOnly(_)) {
                     continue;
                 }
 
@@ -29,7 +41,11 @@ pub(crate) fn serialize<S: Serializer>(
                         serializer.write_text(text)?;
                     }
                     Node::Element(ref elem) => {
-                        let attrs = elem.attrs.iter().map(|(k, v)| (k, &v[..]));
+                        let attrs = elem
+                            .attrs
+                            .iter()
+                            .filter(|(k, _)| keep_attr(k))
+                            .map(|(k, v)| (k, &v[..]));
                         serializer.start_elem(elem.name.clone(), attrs)?;
                     }
                     _ => (),
@@ -37,7 +53,7 @@ pub(crate) fn serialize<S: Serializer>(
             }
 
             Edge::Close(node) => {
-                if node == self_node && traversal_scope == TraversalScope::ChildrenOnly(None) {
+                if node == self_node && matches!(traversal_scope, TraversalScope::ChildrenOnly(_)) {
                     continue;
                 }
 
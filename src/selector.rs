@@ -7,10 +7,11 @@ pub use cssparser::ToCss;
 use html5ever::{LocalName, Namespace};
 use selectors::{
     matching,
-    parser::{self, ParseRelative, SelectorList, SelectorParseErrorKind},
+    parser::{self, Combinator, Component, ParseRelative, SelectorList, SelectorParseErrorKind},
+    visitor::SelectorVisitor,
 };
 
-use crate::error::SelectorErrorKind;
+use crate::error::{MaxSpecificityError, SelectorErrorKind};
 use crate::ElementRef;
 
 /// Wrapper around CSS selectors.
@@ -24,7 +25,7 @@ pub struct Selector {
 
 impl Selector {
     /// Parses a CSS selector group.
-    pub fn parse(selectors: &'_ str) -> Result<Self, SelectorErrorKind> {
+    pub fn parse(selectors: &'_ str) -> Result<Self, SelectorErrorKind<'_>> {
         let mut parser_input = cssparser::ParserInput::new(selectors);
         let mut parser = cssparser::Parser::new(&mut parser_input);
 
@@ -33,6 +34,59 @@ impl Selector {
             .map_err(SelectorErrorKind::from)
     }
 
+    /// Parses a CSS selector group, rejecting it if any selector has more ID, class or type
+    /// components than allowed by `max`, an `(ids, classes, elements)` specificity.
+    ///
+    /// Each component is limited separately. Attribute and pseudo-class selectors count as
+    /// classes.
+    ///
+    /// ```
+    /// use scraper::error::MaxSpecificityError;
+    /// use scraper::Selector;
+    ///
+    /// assert!(Selector::parse_max_specificity(".a li", (0, 1, 1)).is_ok());
+    /// assert!(matches!(
+    ///     Selector::parse_max_specificity("#x", (0, 1, 1)),
+    ///     Err(MaxSpecificityError::TooSpecific(_)),
+    /// ));
+    /// ```
+    pub fn parse_max_specificity(
+        selectors: &'_ str,
+        max: (u32, u32, u32),
+    ) -> Result<Self, MaxSpecificityError<'_>> {
+        let selector = Self::parse(selectors)?;
+        let (ids, classes, elements) = max;
+        let too_specific = selector
+            .specificities()
+            .into_iter()
+            .find(|s| s.ids > ids || s.classes > classes || s.elements > elements);
+        match too_specific {
+            Some(specificity) => Err(MaxSpecificityError::TooSpecific(specificity)),
+            None => Ok(selector),
+        }
+    }
+
+    /// Builds an `[attr="value"]` selector, escaping the attribute name and value.
+    ///
+    /// ```
+    /// use scraper::{Html, Selector};
+    ///
+    /// let fragment = Html::parse_fragment(r#"<p title='Say "hi"'>1</p>"#);
+    /// let selector = Selector::for_attr_value("title", r#"Say "hi""#);
+    ///
+    /// assert!(fragment.exists(&selector));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attribute name is empty.
+    pub fn for_attr_value(attr: &str, value: &str) -> Self {
+        SelectorBuilder::new()
+            .attr_eq(attr, value)
+            .build()
+            .expect("attribute name must not be empty")
+    }
+
     /// Returns true if the element matches this selector.
     pub fn matches(&self, element: &ElementRef) -> bool {
         self.matches_with_scope(element, None)
@@ -57,6 +111,277 @@ impl Selector {
             .iter()
             .any(|s| matching::matches_selector(s, 0, None, element, &mut context))
     }
+
+    /// Returns the ID if this selector is exactly an ID selector such as `#main`.
+    ///
+    /// ```
+    /// use scraper::Selector;
+    ///
+    /// assert_eq!(Selector::parse("#main").unwrap().as_id(), Some("main"));
+    /// assert_eq!(Selector::parse("div#main").unwrap().as_id(), None);
+    /// ```
+    pub fn as_id(&self) -> Option<&str> {
+        match self.selectors.0.as_slice() {
+            [selector] => match selector.iter_raw_match_order().as_slice() {
+                [Component::ID(id)] => Some(&id.0),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns true if the selector uses a dynamic pseudo-class such as `:hover`, `:focus-within`
+    /// or `:target`.
+    ///
+    /// These depend on user interaction or the URL, so they never match elements of a static
+    /// document.
+    ///
+    /// ```
+    /// use scraper::Selector;
+    ///
+    /// assert!(Selector::parse("a:hover").unwrap().has_dynamic_pseudo());
+    /// assert!(!Selector::parse("a:first-child").unwrap().has_dynamic_pseudo());
+    /// ```
+    pub fn has_dynamic_pseudo(&self) -> bool {
+        struct DynamicPseudoVisitor;
+
+        impl SelectorVisitor for DynamicPseudoVisitor {
+            type Impl = Simple;
+
+            // Stops the traversal at the first dynamic pseudo-class.
+            fn visit_simple_selector(&mut self, component: &Component<Simple>) -> bool {
+                !matches!(component, Component::NonTSPseudoClass(_))
+            }
+        }
+
+        self.selectors
+            .0
+            .iter()
+            .any(|selector| !selector.visit(&mut DynamicPseudoVisitor))
+    }
+
+    /// Returns the specificity of each selector in the group.
+    pub fn specificities(&self) -> Vec<Specificity> {
+        self.selectors
+            .0
+            .iter()
+            .map(|s| Specificity::from(s.specificity()))
+            .collect()
+    }
+
+    /// Returns the highest specificity among the selectors in the group which match the element,
+    /// or `None` if none of them match.
+    pub fn matching_specificity(&self, element: &ElementRef) -> Option<Specificity> {
+        let mut nth_index_cache = Default::default();
+        let mut context = matching::MatchingContext::new(
+            matching::MatchingMode::Normal,
+            None,
+            &mut nth_index_cache,
+            matching::QuirksMode::NoQuirks,
+            matching::NeedsSelectorFlags::No,
+            matching::IgnoreNthChildForInvalidation::No,
+        );
+        self.selectors
+            .0
+            .iter()
+            .filter(|s| matching::matches_selector(s, 0, None, element, &mut context))
+            .map(|s| Specificity::from(s.specificity()))
+            .max()
+    }
+
+    /// Splits each selector of the group into its compound selectors, from right to left.
+    ///
+    /// Each compound is paired with the combinator linking it to the compound on its left.
+    pub(crate) fn compound_chains(&self) -> Vec<Vec<(Compound<'_>, Option<Combinator>)>> {
+        self.selectors
+            .0
+            .iter()
+            .map(|selector| {
+                let mut offsets = vec![0];
+                let mut combinators = Vec::new();
+                for (i, component) in selector.iter_raw_parse_order_from(0).enumerate() {
+                    if let Component::Combinator(combinator) = component {
+                        offsets.push(i + 1);
+                        combinators.push(*combinator);
+                    }
+                }
+                offsets
+                    .into_iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(i, offset)| {
+                        let combinator = i.checked_sub(1).map(|i| combinators[i]);
+                        (Compound { selector, offset }, combinator)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A compound selector within a complex selector, starting at `offset` in parse order.
+pub(crate) struct Compound<'a> {
+    selector: &'a parser::Selector<Simple>,
+    offset: usize,
+}
+
+impl Compound<'_> {
+    /// Returns true if the element matches every simple selector of the compound.
+    pub(crate) fn matches(&self, element: &ElementRef) -> bool {
+        let mut nth_index_cache = Default::default();
+        let mut context = matching::MatchingContext::new(
+            matching::MatchingMode::Normal,
+            None,
+            &mut nth_index_cache,
+            matching::QuirksMode::NoQuirks,
+            matching::NeedsSelectorFlags::No,
+            matching::IgnoreNthChildForInvalidation::No,
+        );
+        !matches!(
+            matching::matches_compound_selector_from(
+                self.selector,
+                self.offset,
+                &mut context,
+                element
+            ),
+            matching::CompoundSelectorMatchingResult::NotMatched
+        )
+    }
+}
+
+/// Builder composing a selector from escaped parts.
+///
+/// ```
+/// use scraper::selector::SelectorBuilder;
+/// use scraper::Selector;
+///
+/// let mut builder = SelectorBuilder::new();
+/// builder.tag("div").class("x").descendant().tag("a");
+///
+/// assert_eq!(builder.build().unwrap(), Selector::parse("div.x a").unwrap());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SelectorBuilder {
+    css: String,
+}
+
+impl SelectorBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a type selector.
+    pub fn tag(&mut self, name: &str) -> &mut Self {
+        cssparser::serialize_identifier(name, &mut self.css).unwrap();
+        self
+    }
+
+    /// Appends the universal selector `*`.
+    pub fn any(&mut self) -> &mut Self {
+        self.css.push('*');
+        self
+    }
+
+    /// Appends an ID selector.
+    pub fn id(&mut self, id: &str) -> &mut Self {
+        self.css.push('#');
+        cssparser::serialize_identifier(id, &mut self.css).unwrap();
+        self
+    }
+
+    /// Appends a class selector.
+    pub fn class(&mut self, class: &str) -> &mut Self {
+        self.css.push('.');
+        cssparser::serialize_identifier(class, &mut self.css).unwrap();
+        self
+    }
+
+    /// Appends an attribute presence selector such as `[href]`.
+    pub fn attr(&mut self, name: &str) -> &mut Self {
+        self.css.push('[');
+        cssparser::serialize_identifier(name, &mut self.css).unwrap();
+        self.css.push(']');
+        self
+    }
+
+    /// Appends an attribute value selector such as `[href="/"]`.
+    pub fn attr_eq(&mut self, name: &str, value: &str) -> &mut Self {
+        self.css.push('[');
+        cssparser::serialize_identifier(name, &mut self.css).unwrap();
+        self.css.push('=');
+        cssparser::serialize_string(value, &mut self.css).unwrap();
+        self.css.push(']');
+        self
+    }
+
+    /// Appends a descendant combinator.
+    pub fn descendant(&mut self) -> &mut Self {
+        self.css.push(' ');
+        self
+    }
+
+    /// Appends a child combinator.
+    pub fn child(&mut self) -> &mut Self {
+        self.css.push_str(" > ");
+        self
+    }
+
+    /// Appends a next-sibling combinator.
+    pub fn next_sibling(&mut self) -> &mut Self {
+        self.css.push_str(" + ");
+        self
+    }
+
+    /// Appends a subsequent-sibling combinator.
+    pub fn subsequent_sibling(&mut self) -> &mut Self {
+        self.css.push_str(" ~ ");
+        self
+    }
+
+    /// Starts another selector of the group.
+    pub fn or(&mut self) -> &mut Self {
+        self.css.push_str(", ");
+        self
+    }
+
+    /// Returns the selector composed so far.
+    pub fn as_str(&self) -> &str {
+        &self.css
+    }
+
+    /// Parses the composed selector.
+    ///
+    /// Fails if the parts are not composed into a valid selector, e.g. when it ends with a
+    /// combinator.
+    pub fn build(&self) -> Result<Selector, SelectorErrorKind<'_>> {
+        Selector::parse(&self.css)
+    }
+}
+
+/// The specificity of a selector, ordered as in the cascade.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Specificity {
+    /// The number of ID selectors.
+    pub ids: u32,
+
+    /// The number of class, attribute and pseudo-class selectors.
+    pub classes: u32,
+
+    /// The number of type and pseudo-element selectors.
+    pub elements: u32,
+}
+
+impl From<u32> for Specificity {
+    // `selectors` packs the three components into ten bits each.
+    fn from(packed: u32) -> Self {
+        const MAX_10BIT: u32 = (1 << 10) - 1;
+        Specificity {
+            ids: packed >> 20,
+            classes: (packed >> 10) & MAX_10BIT,
+            elements: packed & MAX_10BIT,
+        }
+    }
 }
 
 impl ToCss for Selector {
@@ -74,9 +399,36 @@ pub struct Parser;
 impl<'i> parser::Parser<'i> for Parser {
     type Impl = Simple;
     type Error = SelectorParseErrorKind<'i>;
+
+    // No default namespace is declared, so unprefixed type selectors match elements in any
+    // namespace, as in browsers. The well-known prefixes allow restricting a match, e.g.
+    // `html|a` does not match an SVG `<a>`.
+    fn namespace_for_prefix(&self, prefix: &CssLocalName) -> Option<Namespace> {
+        match &*prefix.0 {
+            "html" => Some(ns!(html)),
+            "svg" => Some(ns!(svg)),
+            "math" | "mathml" => Some(ns!(mathml)),
+            "xlink" => Some(ns!(xlink)),
+            "xml" => Some(ns!(xml)),
+            _ => None,
+        }
+    }
+
+    fn parse_non_ts_pseudo_class(
+        &self,
+        location: cssparser::SourceLocation,
+        name: cssparser::CowRcStr<'i>,
+    ) -> Result<NonTSPseudoClass, cssparser::ParseError<'i, Self::Error>> {
+        NonTSPseudoClass::from_name(&name).ok_or_else(|| {
+            location.new_custom_error(SelectorParseErrorKind::UnsupportedPseudoClassOrElement(
+                name,
+            ))
+        })
+    }
 }
 
-/// A simple implementation of `SelectorImpl` with no pseudo-classes or pseudo-elements.
+/// A simple implementation of `SelectorImpl` with only dynamic pseudo-classes and no
+/// pseudo-elements.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Simple;
 
@@ -141,18 +493,64 @@ impl ToCss for CssLocalName {
 }
 
 /// Non Tree-Structural Pseudo-Class.
+///
+/// Only dynamic pseudo-classes are supported. They are parsed so that selectors written for
+/// browsers are accepted, but never match, since a static document has no user interaction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum NonTSPseudoClass {}
+pub enum NonTSPseudoClass {
+    /// `:active`
+    Active,
+    /// `:focus`
+    Focus,
+    /// `:focus-visible`
+    FocusVisible,
+    /// `:focus-within`
+    FocusWithin,
+    /// `:hover`
+    Hover,
+    /// `:target`
+    Target,
+    /// `:visited`
+    Visited,
+}
+
+impl NonTSPseudoClass {
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        Some(match &*name {
+            "active" => NonTSPseudoClass::Active,
+            "focus" => NonTSPseudoClass::Focus,
+            "focus-visible" => NonTSPseudoClass::FocusVisible,
+            "focus-within" => NonTSPseudoClass::FocusWithin,
+            "hover" => NonTSPseudoClass::Hover,
+            "target" => NonTSPseudoClass::Target,
+            "visited" => NonTSPseudoClass::Visited,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            NonTSPseudoClass::Active => "active",
+            NonTSPseudoClass::Focus => "focus",
+            NonTSPseudoClass::FocusVisible => "focus-visible",
+            NonTSPseudoClass::FocusWithin => "focus-within",
+            NonTSPseudoClass::Hover => "hover",
+            NonTSPseudoClass::Target => "target",
+            NonTSPseudoClass::Visited => "visited",
+        }
+    }
+}
 
 impl parser::NonTSPseudoClass for NonTSPseudoClass {
     type Impl = Simple;
 
     fn is_active_or_hover(&self) -> bool {
-        false
+        matches!(self, NonTSPseudoClass::Active | NonTSPseudoClass::Hover)
     }
 
     fn is_user_action_state(&self) -> bool {
-        false
+        !matches!(self, NonTSPseudoClass::Target | NonTSPseudoClass::Visited)
     }
 }
 
@@ -161,7 +559,8 @@ impl ToCss for NonTSPseudoClass {
     where
         W: fmt::Write,
     {
-        dest.write_str("")
+        dest.write_char(':')?;
+        dest.write_str(self.name())
     }
 }
 
@@ -204,6 +603,157 @@ mod tests {
         let _sel: Selector = (*s).try_into().unwrap();
     }
 
+    #[test]
+    fn specificity_of_each_selector() {
+        let selector = Selector::parse("#a .b, li, ul li.c[d]").unwrap();
+        let specificities: Vec<_> = selector
+            .specificities()
+            .into_iter()
+            .map(|s| (s.ids, s.classes, s.elements))
+            .collect();
+        assert_eq!(specificities, [(1, 1, 0), (0, 0, 1), (0, 2, 2)]);
+    }
+
+    #[test]
+    fn as_id_only_for_pure_id_selectors() {
+        let as_id = |s: &str| Selector::parse(s).unwrap().as_id().map(str::to_owned);
+        assert_eq!(as_id("#main").as_deref(), Some("main"));
+        assert_eq!(as_id(r"#\31 st").as_deref(), Some("1st"));
+        assert_eq!(as_id("div#main"), None);
+        assert_eq!(as_id("#main.x"), None);
+        assert_eq!(as_id("#a, #b"), None);
+        assert_eq!(as_id("body #main"), None);
+        assert_eq!(as_id(".main"), None);
+    }
+
+    #[test]
+    fn builder_escapes_parts() {
+        let mut builder = SelectorBuilder::new();
+        builder
+            .tag("ul")
+            .id("1st")
+            .child()
+            .any()
+            .class("a:b")
+            .attr_eq("data-x", r#"say "hi""#)
+            .or()
+            .attr("href")
+            .next_sibling()
+            .tag("p")
+            .subsequent_sibling()
+            .tag("i");
+        assert_eq!(
+            builder.as_str(),
+            r#"ul#\31 st > *.a\:b[data-x="say \"hi\""], [href] + p ~ i"#
+        );
+
+        let selector = builder.build().unwrap();
+        let html = crate::Html::parse_fragment(
+            r#"<ul id="1st"><li class="a:b" data-x='say "hi"'></li></ul>"#,
+        );
+        assert_eq!(html.select(&selector).count(), 1);
+    }
+
+    #[test]
+    fn builder_with_trailing_combinator() {
+        let mut builder = SelectorBuilder::new();
+        builder.tag("div").child();
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn max_specificity_per_component() {
+        let parse = |css| Selector::parse_max_specificity(css, (0, 2, 1));
+
+        assert!(parse(".a").is_ok());
+        assert!(parse("li.a[href]").is_ok());
+        assert!(parse(".a:first-child, p").is_ok());
+        assert!(parse("*").is_ok());
+
+        for css in ["#x", "p li.a", ".a.b.c", "li, #y", "[a][b]:hover"] {
+            assert!(
+                matches!(parse(css), Err(MaxSpecificityError::TooSpecific(_))),
+                "{}",
+                css
+            );
+        }
+        match parse("ul li") {
+            Err(MaxSpecificityError::TooSpecific(s)) => {
+                assert_eq!((s.ids, s.classes, s.elements), (0, 0, 2))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        // Invalid selectors fail to parse before their specificity is checked.
+        let error = parse("<p>").unwrap_err();
+        assert!(
+            matches!(error, MaxSpecificityError::Invalid(_)),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn for_attr_value_escapes() {
+        use crate::Html;
+
+        let fragment = Html::parse_fragment(
+            r#"<a data-q='x"] , *[y' id="1"></a><a data-q="back\slash" id="2"></a><a data-q="x" id="3"></a>"#,
+        );
+        let ids = |selector: Selector| {
+            fragment
+                .select(&selector)
+                .filter_map(|element| element.value().id())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(Selector::for_attr_value("data-q", r#"x"] , *[y"#)),
+            ["1"]
+        );
+        assert_eq!(
+            ids(Selector::for_attr_value("data-q", r"back\slash")),
+            ["2"]
+        );
+        assert_eq!(ids(Selector::for_attr_value("data-q", "x")), ["3"]);
+        assert_eq!(
+            ids(Selector::for_attr_value("data q", "x")),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn dynamic_pseudo_classes_parse_but_never_match() {
+        use crate::Html;
+
+        let fragment = Html::parse_fragment(r#"<ul><li><a href="/">Home</a></li></ul>"#);
+        for (css, count) in [
+            ("a:hover", 0),
+            ("li:FOCUS-WITHIN", 0),
+            (":target", 0),
+            ("ul > li:focus", 0),
+            (":not(:not(a:hover))", 0),
+            ("a:not(:visited)", 1),
+            ("a, li:active", 1),
+        ] {
+            let selector = Selector::parse(css).unwrap();
+            assert!(selector.has_dynamic_pseudo(), "{}", css);
+            assert_eq!(fragment.select(&selector).count(), count, "{}", css);
+        }
+
+        for css in ["a", "li:first-child", ":not(a)", "ul > li, a"] {
+            assert!(
+                !Selector::parse(css).unwrap().has_dynamic_pseudo(),
+                "{}",
+                css
+            );
+        }
+        assert!(Selector::parse(":checked").is_err());
+        assert_eq!(
+            Selector::parse("a:Hover").unwrap().to_css_string(),
+            "a:hover"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn invalid_selector_conversions() {
@@ -0,0 +1,200 @@
+//! Minimal style rules for approximating the cascade.
+
+use crate::selector::{Selector, Specificity};
+use crate::ElementRef;
+
+/// A block of CSS declarations such as the contents of a `style` attribute.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Declarations {
+    declarations: Vec<(String, String)>,
+}
+
+impl Declarations {
+    /// Parses a declaration block such as `color: red; margin: 0`.
+    ///
+    /// Property names are lowercased. Malformed declarations are skipped. Semicolons inside
+    /// quotes or parentheses do not end a declaration.
+    pub fn parse(block: &str) -> Self {
+        let mut declarations = Vec::new();
+
+        for declaration in split_outside_nesting(block, ';') {
+            if let Some((name, value)) = declaration.split_once(':') {
+                let name = name.trim();
+                let value = value.trim();
+                if !name.is_empty() && !value.is_empty() {
+                    declarations.push((name.to_ascii_lowercase(), value.to_owned()));
+                }
+            }
+        }
+
+        Declarations { declarations }
+    }
+
+    /// Returns the value of the last declaration of a property.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.declarations
+            .iter()
+            .rev()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the declarations as `(name, value)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns true if there are no declarations.
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+fn split_outside_nesting(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, c) if c == separator && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+
+    parts
+}
+
+/// An ordered list of style rules.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StyleSheet {
+    rules: Vec<(Selector, Declarations)>,
+}
+
+impl StyleSheet {
+    /// Creates an empty style sheet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a rule to the style sheet.
+    pub fn add_rule(&mut self, selector: Selector, declarations: Declarations) {
+        self.rules.push((selector, declarations));
+    }
+
+    /// Returns the declarations of the rules matching an element in cascade order.
+    ///
+    /// Rules are sorted by specificity and then by their order in the style sheet, so that a
+    /// later entry takes precedence over an earlier one.
+    ///
+    /// ```
+    /// use scraper::stylesheet::{Declarations, StyleSheet};
+    /// use scraper::{Html, Selector};
+    ///
+    /// let mut style_sheet = StyleSheet::new();
+    /// style_sheet.add_rule(Selector::parse("p.note").unwrap(), Declarations::parse("color: red"));
+    /// style_sheet.add_rule(Selector::parse("p").unwrap(), Declarations::parse("color: blue"));
+    ///
+    /// let fragment = Html::parse_fragment(r#"<p class="note">Hello</p>"#);
+    /// let p = fragment.select(&Selector::parse("p").unwrap()).next().unwrap();
+    ///
+    /// let (_, declarations) = style_sheet.matching_declarations(&p).pop().unwrap();
+    /// assert_eq!(declarations.get("color"), Some("red"));
+    /// ```
+    pub fn matching_declarations(&self, element: &ElementRef) -> Vec<(Specificity, &Declarations)> {
+        let mut matching: Vec<_> = self
+            .rules
+            .iter()
+            .filter_map(|(selector, declarations)| {
+                selector
+                    .matching_specificity(element)
+                    .map(|specificity| (specificity, declarations))
+            })
+            .collect();
+
+        // A stable sort keeps rules of equal specificity in style sheet order.
+        matching.sort_by_key(|(specificity, _)| *specificity);
+
+        matching
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Declarations, StyleSheet};
+    use crate::{Html, Selector};
+
+    #[test]
+    fn parse_declarations() {
+        let declarations =
+            Declarations::parse(r#"COLOR: red; background: url("a;b.png") ; bad; margin:0;"#);
+        let parsed: Vec<_> = declarations.iter().collect();
+        assert_eq!(
+            parsed,
+            [
+                ("color", "red"),
+                ("background", r#"url("a;b.png")"#),
+                ("margin", "0")
+            ]
+        );
+        assert_eq!(declarations.get("Color"), Some("red"));
+    }
+
+    #[test]
+    fn rules_in_specificity_order() {
+        let mut style_sheet = StyleSheet::new();
+        style_sheet.add_rule(
+            Selector::parse("#main").unwrap(),
+            Declarations::parse("color: green"),
+        );
+        style_sheet.add_rule(
+            Selector::parse("div, .box").unwrap(),
+            Declarations::parse("color: red"),
+        );
+        style_sheet.add_rule(
+            Selector::parse("div").unwrap(),
+            Declarations::parse("color: blue"),
+        );
+        style_sheet.add_rule(
+            Selector::parse("span").unwrap(),
+            Declarations::parse("color: black"),
+        );
+
+        let fragment = Html::parse_fragment(r#"<div id="main" class="box"></div>"#);
+        let div = fragment
+            .select(&Selector::parse("div").unwrap())
+            .next()
+            .unwrap();
+
+        let colors: Vec<_> = style_sheet
+            .matching_declarations(&div)
+            .into_iter()
+            .map(|(specificity, declarations)| {
+                (
+                    (specificity.ids, specificity.classes, specificity.elements),
+                    declarations.get("color").unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            colors,
+            [
+                ((0, 0, 1), "blue"),
+                ((0, 1, 0), "red"),
+                ((1, 0, 0), "green")
+            ]
+        );
+    }
+}